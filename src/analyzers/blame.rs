@@ -1,26 +1,16 @@
-use crate::types::{Commit, SiloData};
+use crate::types::{AuthorRisk, Commit, OwnershipData, SiloData};
 use std::collections::{HashMap, HashSet};
 
+/// Authors inactive this long (unless configured) leave their files orphaned.
+pub const DEFAULT_ORPHAN_DAYS: i64 = 180;
+
+/// Minimum share of a file's commits that makes an author one of its primary authors.
+const PRIMARY_SHARE: f64 = 0.25;
+
 /// Analyzes author concentration per file using commit history.
 /// High single-author ratio = knowledge silo = elevated bug risk.
 pub fn analyze_authors(commits: &[Commit], files: &[String]) -> HashMap<String, SiloData> {
-    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
-
-    // filename → author → commit count
-    let mut file_authors: HashMap<String, HashMap<String, usize>> = HashMap::new();
-
-    for commit in commits {
-        for file in &commit.files {
-            if !file_set.contains(file.as_str()) {
-                continue;
-            }
-            *file_authors
-                .entry(file.clone())
-                .or_default()
-                .entry(commit.author.clone())
-                .or_insert(0) += 1;
-        }
-    }
+    let file_authors = file_author_counts(commits, files);
 
     files
         .iter()
@@ -62,3 +52,155 @@ pub fn analyze_authors(commits: &[Commit], files: &[String]) -> HashMap<String,
         })
         .collect()
 }
+
+/// Finds files whose primary authors have all stopped committing, and for
+/// each author the files where they are the only primary author.
+///
+/// Primary authors hold at least a quarter of a file's commits (the top
+/// author always counts). An author is inactive when their last commit
+/// anywhere in the analyzed history is `inactive_days` old or more.
+pub fn analyze_turnover(
+    commits: &[Commit],
+    files: &[String],
+    inactive_days: i64,
+) -> (HashMap<String, OwnershipData>, Vec<AuthorRisk>) {
+    let now = chrono::Utc::now().timestamp();
+    let mut last_commit: HashMap<&str, i64> = HashMap::new();
+    for commit in commits {
+        let t = last_commit
+            .entry(commit.author.as_str())
+            .or_insert(commit.timestamp);
+        *t = (*t).max(commit.timestamp);
+    }
+    let idle_days = |author: &str| {
+        last_commit
+            .get(author)
+            .map_or(i64::MAX, |t| ((now - t) / 86400).max(0))
+    };
+
+    let file_authors = file_author_counts(commits, files);
+    let mut ownership = HashMap::new();
+    // author → (files they alone hold, commit count in each)
+    let mut sole: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+    for (file, authors) in file_authors {
+        let total: usize = authors.values().sum();
+        let mut ranked: Vec<(String, usize)> = authors.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let primary: Vec<String> = ranked
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, n))| *i == 0 || *n as f64 / total as f64 >= PRIMARY_SHARE)
+            .map(|(_, (a, _))| a.clone())
+            .collect();
+        let primary_last_active_days = primary.iter().map(|a| idle_days(a)).min().unwrap_or(0);
+        if let [only] = primary.as_slice() {
+            sole.entry(only.clone())
+                .or_default()
+                .push((file.clone(), total));
+        }
+        let data = OwnershipData {
+            primary_authors: primary,
+            primary_last_active_days,
+            orphaned: primary_last_active_days >= inactive_days,
+        };
+        ownership.insert(file, data);
+    }
+
+    let mut risks: Vec<AuthorRisk> = sole
+        .into_iter()
+        .map(|(author, mut held)| {
+            held.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let last_active_days = idle_days(&author);
+            AuthorRisk {
+                author,
+                last_active_days,
+                inactive: last_active_days >= inactive_days,
+                files: held.into_iter().map(|(f, _)| f).collect(),
+            }
+        })
+        .collect();
+    risks.sort_by(|a, b| {
+        b.files
+            .len()
+            .cmp(&a.files.len())
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    (ownership, risks)
+}
+
+/// filename → author → commit count, for files in `files` only.
+fn file_author_counts(
+    commits: &[Commit],
+    files: &[String],
+) -> HashMap<String, HashMap<String, usize>> {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let mut file_authors: HashMap<String, HashMap<String, usize>> = HashMap::new();
+
+    for commit in commits {
+        for file in &commit.files {
+            if !file_set.contains(file.as_str()) {
+                continue;
+            }
+            *file_authors
+                .entry(file.clone())
+                .or_default()
+                .entry(commit.author.clone())
+                .or_insert(0) += 1;
+        }
+    }
+    file_authors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, days_ago: i64, files: &[&str]) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            author: author.to_string(),
+            timestamp: chrono::Utc::now().timestamp() - days_ago * 86400,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_file_orphaned_when_all_primary_authors_left() {
+        let commits = vec![
+            commit("gone", 400, &["legacy.rs"]),
+            commit("gone", 390, &["legacy.rs", "shared.rs"]),
+            commit("gone", 380, &["legacy.rs"]),
+            commit("gone", 370, &["legacy.rs"]),
+            commit("drive-by", 10, &["legacy.rs"]),
+            commit("here", 5, &["shared.rs"]),
+            commit("here", 3, &["shared.rs"]),
+        ];
+        let files: Vec<String> = vec!["legacy.rs".into(), "shared.rs".into()];
+        let (ownership, risks) = analyze_turnover(&commits, &files, DEFAULT_ORPHAN_DAYS);
+
+        let legacy = &ownership["legacy.rs"];
+        assert_eq!(legacy.primary_authors, vec!["gone".to_string()]);
+        assert!(
+            legacy.orphaned,
+            "A recent drive-by commit does not make it owned"
+        );
+        assert_eq!(legacy.primary_last_active_days, 370);
+
+        let shared = &ownership["shared.rs"];
+        assert_eq!(
+            shared.primary_authors,
+            vec!["here".to_string(), "gone".to_string()]
+        );
+        assert!(!shared.orphaned);
+
+        assert_eq!(
+            risks.len(),
+            1,
+            "Only files with a single primary author are at risk"
+        );
+        assert_eq!(risks[0].author, "gone");
+        assert!(risks[0].inactive);
+        assert_eq!(risks[0].files, vec!["legacy.rs".to_string()]);
+    }
+}
@@ -6,6 +6,11 @@ static BUG_PATTERN: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy:
         .unwrap()
 });
 
+/// Returns true when a commit subject reads like a bug fix.
+pub fn is_bug_fix(subject: &str) -> bool {
+    BUG_PATTERN.is_match(subject)
+}
+
 /// Identifies files that frequently appear in bug-fix commits.
 pub fn analyze_bug_correlation(commits: &[Commit], files: &[String]) -> HashMap<String, BugData> {
     let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
@@ -13,7 +18,7 @@ pub fn analyze_bug_correlation(commits: &[Commit], files: &[String]) -> HashMap<
     let mut file_bug_counts: HashMap<String, usize> = HashMap::new();
 
     for commit in commits {
-        if !BUG_PATTERN.is_match(&commit.subject) {
+        if !is_bug_fix(&commit.subject) {
             continue;
         }
         for file in &commit.files {
@@ -109,7 +114,7 @@ mod tests {
         ];
         let files = vec!["a.rs".to_string(), "b.rs".to_string()];
         let result = analyze_bug_correlation(&commits, &files);
-        for (_, data) in &result {
+        for data in result.values() {
             assert!(
                 data.bug_score >= 0.0 && data.bug_score <= 100.0,
                 "bug_score {} out of range",
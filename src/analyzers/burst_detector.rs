@@ -149,7 +149,7 @@ mod tests {
         let result = analyze_bursts(&commits, &files);
         let score = result["a.rs"].burst_score;
         assert!(
-            score >= 0.0 && score <= 100.0,
+            (0.0..=100.0).contains(&score),
             "burst_score {} out of range",
             score
         );
@@ -2,7 +2,7 @@ use crate::types::{ChurnData, Commit};
 use std::collections::{HashMap, HashSet};
 
 // Exponential decay: λ = 0.005 → half-life ≈ 139 days
-const DECAY_LAMBDA: f64 = 0.005;
+pub const DECAY_LAMBDA: f64 = 0.005;
 
 /// Calculates churn rate with recency decay per file.
 /// Recent commits contribute exponentially more to the weighted score.
@@ -75,7 +75,7 @@ mod tests {
             "src/c.rs".to_string(),
         ];
         let result = analyze_churn(&commits, &files);
-        for (_, data) in &result {
+        for data in result.values() {
             assert!(
                 data.weighted_score >= 0.0 && data.weighted_score <= 100.0,
                 "weighted_score {} out of range",
@@ -0,0 +1,148 @@
+use crate::git::tree::blame_line_times;
+use crate::types::{AgeClass, CodeAgeData, Commit};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Files first seen within this many days are `new`.
+const NEW_DAYS: i64 = 90;
+
+/// Untouched for this many days (unless configured) counts as `stable`.
+pub const DEFAULT_STABLE_DAYS: i64 = 365;
+
+/// Measures how long ago each file was first and last changed and classifies it.
+///
+/// Ages are bounded by the analyzed history, so with `--since` a file's
+/// first-seen age is at most the window length. A file untouched for
+/// `stable_days` is `stable`, and for twice that `abandoned`.
+pub fn analyze_code_age(
+    commits: &[Commit],
+    files: &[String],
+    stable_days: i64,
+) -> HashMap<String, CodeAgeData> {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let now = chrono::Utc::now().timestamp();
+
+    // filename → (first seen, last modified)
+    let mut seen: HashMap<&str, (i64, i64)> = HashMap::new();
+    for commit in commits {
+        for file in &commit.files {
+            if !file_set.contains(file.as_str()) {
+                continue;
+            }
+            let e = seen
+                .entry(file.as_str())
+                .or_insert((commit.timestamp, commit.timestamp));
+            e.0 = e.0.min(commit.timestamp);
+            e.1 = e.1.max(commit.timestamp);
+        }
+    }
+
+    files
+        .iter()
+        .filter_map(|file| {
+            let (first, last) = seen.get(file.as_str())?;
+            let first_seen_days = ((now - first) / 86400).max(0);
+            let last_modified_days = ((now - last) / 86400).max(0);
+            let data = CodeAgeData {
+                last_modified_days,
+                first_seen_days,
+                median_line_age_days: None,
+                age_class: classify(first_seen_days, last_modified_days, stable_days),
+            };
+            Some((file.clone(), data))
+        })
+        .collect()
+}
+
+/// Median age in days of the lines of each of `files` at HEAD, from
+/// `git blame`. Blame is slow, so callers pass only the files they report.
+/// Files that cannot be blamed (deleted, binary) are left out.
+pub fn median_line_ages(repo: &Path, files: &[String]) -> HashMap<String, i64> {
+    let now = chrono::Utc::now().timestamp();
+    files
+        .par_iter()
+        .filter_map(|file| {
+            let hunks = blame_line_times(repo, file).ok()?;
+            let median = median_time(hunks)?;
+            Some((file.clone(), ((now - median) / 86400).max(0)))
+        })
+        .collect()
+}
+
+fn classify(first_seen_days: i64, last_modified_days: i64, stable_days: i64) -> AgeClass {
+    if first_seen_days < NEW_DAYS {
+        AgeClass::New
+    } else if last_modified_days < stable_days {
+        AgeClass::Active
+    } else if last_modified_days < stable_days * 2 {
+        AgeClass::Stable
+    } else {
+        AgeClass::Abandoned
+    }
+}
+
+/// Median timestamp over `(time, line count)` hunks, weighting each by its lines.
+fn median_time(mut hunks: Vec<(i64, usize)>) -> Option<i64> {
+    let total: usize = hunks.iter().map(|(_, n)| n).sum();
+    if total == 0 {
+        return None;
+    }
+    hunks.sort_unstable();
+    let mut seen = 0;
+    for (t, n) in hunks {
+        seen += n;
+        if seen * 2 >= total {
+            return Some(t);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classification_thresholds() {
+        assert_eq!(classify(30, 5, 365), AgeClass::New);
+        assert_eq!(classify(800, 10, 365), AgeClass::Active);
+        assert_eq!(classify(800, 400, 365), AgeClass::Stable);
+        assert_eq!(classify(2000, 800, 365), AgeClass::Abandoned);
+        assert_eq!(
+            classify(800, 100, 90),
+            AgeClass::Stable,
+            "Stable period is configurable"
+        );
+    }
+
+    #[test]
+    fn test_ages_from_commits() {
+        let now = chrono::Utc::now().timestamp();
+        let commit = |days: i64, files: &[&str]| Commit {
+            hash: "abc".to_string(),
+            author: "dev".to_string(),
+            timestamp: now - days * 86400,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        };
+        let commits = vec![commit(500, &["a.rs", "b.rs"]), commit(10, &["a.rs"])];
+        let files: Vec<String> = vec!["a.rs".into(), "b.rs".into(), "c.rs".into()];
+        let ages = analyze_code_age(&commits, &files, DEFAULT_STABLE_DAYS);
+        assert_eq!(ages["a.rs"].first_seen_days, 500);
+        assert_eq!(ages["a.rs"].last_modified_days, 10);
+        assert_eq!(ages["a.rs"].age_class, AgeClass::Active);
+        assert_eq!(ages["b.rs"].age_class, AgeClass::Stable);
+        assert!(
+            !ages.contains_key("c.rs"),
+            "Never-changed files have no age"
+        );
+    }
+
+    #[test]
+    fn test_median_time_weights_by_lines() {
+        assert_eq!(median_time(vec![(300, 1), (100, 5), (200, 1)]), Some(100));
+        assert_eq!(median_time(vec![(100, 1), (200, 1)]), Some(100));
+        assert_eq!(median_time(Vec::new()), None);
+    }
+}
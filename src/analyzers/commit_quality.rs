@@ -11,6 +11,12 @@ static WIP_PATTERN: Lazy<Regex> = Lazy::new(|| {
 const LARGE_COMMIT_THRESHOLD: usize = 30;
 const SHORT_MSG_MIN_LENGTH: usize = 10;
 
+/// WIP keyword or a message too short to say anything.
+pub fn is_wip(subject: &str) -> bool {
+    let subj = subject.trim();
+    WIP_PATTERN.is_match(subj) || subj.len() < SHORT_MSG_MIN_LENGTH
+}
+
 /// Tracks per-file involvement in low-quality commits (WIP/short messages)
 /// and oversized commits (mass reformats, merge-all).
 pub fn analyze_commit_quality(
@@ -23,8 +29,7 @@ pub fn analyze_commit_quality(
     let mut large_counts: HashMap<String, usize> = HashMap::new();
 
     for commit in commits {
-        let subj = commit.subject.trim();
-        let is_wip = WIP_PATTERN.is_match(subj) || subj.len() < SHORT_MSG_MIN_LENGTH;
+        let is_wip = is_wip(&commit.subject);
         let is_large = commit.files.len() > LARGE_COMMIT_THRESHOLD;
 
         for file in &commit.files {
@@ -135,7 +140,7 @@ mod tests {
         let result = analyze_commit_quality(&commits, &files);
         let score = result["a.rs"].commit_quality_score;
         assert!(
-            score >= 0.0 && score <= 100.0,
+            (0.0..=100.0).contains(&score),
             "commit_quality_score {} out of range",
             score
         );
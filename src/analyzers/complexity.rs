@@ -0,0 +1,211 @@
+use crate::types::ComplexityData;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Share of each metric in the combined complexity score.
+const LOC_WEIGHT: f64 = 0.4;
+const INDENT_WEIGHT: f64 = 0.4;
+const FUNCTION_WEIGHT: f64 = 0.2;
+
+/// Lines that open a function or method in the languages we see most often.
+/// Deliberately loose — this is a heuristic, not a parser.
+static FUNCTION_START: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:pub(?:\([^)]*\))?|public|private|protected|internal|static|async|export|default|final|override|virtual|unsafe|const|extern)\s+)*(?:fn|def|func|function|sub|fun)\b|^\s*(?:[\w<>\[\],.?]+\s+)+\w+\s*\([^;]*\)\s*(?:throws [\w., ]+)?\s*\{\s*$",
+    )
+    .expect("function start regex")
+});
+
+/// Scores each of `files` from the metrics [`measure`] took of its content at
+/// HEAD.
+///
+/// Language-agnostic: complexity is measured by indentation depth (deeply
+/// nested code is harder to change safely), which correlates well with
+/// cyclomatic complexity without needing a parser per language. Files
+/// missing from `measured` (deleted at HEAD) score zero.
+pub fn analyze_complexity(
+    measured: &HashMap<String, ComplexityData>,
+    files: &[String],
+) -> HashMap<String, ComplexityData> {
+    let raw: Vec<&ComplexityData> = files.iter().filter_map(|f| measured.get(f)).collect();
+
+    // Log-scaled so one 10k-line file does not flatten everything else to zero.
+    let max_of = |pick: fn(&ComplexityData) -> usize| {
+        raw.iter()
+            .map(|m| (pick(m) as f64).ln_1p())
+            .fold(0.0001_f64, f64::max)
+    };
+    let max_loc = max_of(|m| m.loc);
+    let max_indent = max_of(|m| m.total_indent);
+    let max_fn = max_of(|m| m.longest_function);
+
+    files
+        .iter()
+        .map(|file| {
+            let mut data = measured.get(file).cloned().unwrap_or_default();
+            let score = ((data.loc as f64).ln_1p() / max_loc * LOC_WEIGHT
+                + (data.total_indent as f64).ln_1p() / max_indent * INDENT_WEIGHT
+                + (data.longest_function as f64).ln_1p() / max_fn * FUNCTION_WEIGHT)
+                * 100.0;
+            data.complexity_score = score.min(100.0);
+            (file.clone(), data)
+        })
+        .collect()
+}
+
+/// Measures lines of code, indentation and the longest function of one file's
+/// content; `complexity_score` is left at zero for [`analyze_complexity`].
+pub fn measure(content: &str) -> ComplexityData {
+    let unit = indent_unit(content);
+    let lines: Vec<(usize, &str)> = content
+        .lines()
+        .map(|l| (indent_level(l, unit), l.trim()))
+        .collect();
+
+    let mut loc = 0;
+    let mut total_indent = 0;
+    let mut max_indent = 0;
+    for (level, text) in &lines {
+        if text.is_empty() || is_comment(text) {
+            continue;
+        }
+        loc += 1;
+        total_indent += level;
+        max_indent = max_indent.max(*level);
+    }
+    ComplexityData {
+        loc,
+        total_indent,
+        max_indent,
+        longest_function: longest_function(&lines),
+        complexity_score: 0.0,
+    }
+}
+
+/// Length of the longest block opened by a function signature. A block ends
+/// at the next code line indented no deeper than its signature; a closing
+/// bracket at that level is counted as part of the function.
+fn longest_function(lines: &[(usize, &str)]) -> usize {
+    let mut longest = 0;
+    for (i, (start_level, text)) in lines.iter().enumerate() {
+        if !FUNCTION_START.is_match(text) {
+            continue;
+        }
+        let mut len = 1;
+        for (level, body) in &lines[i + 1..] {
+            if body.is_empty() {
+                len += 1;
+                continue;
+            }
+            if level <= start_level {
+                if body.starts_with(['}', ')', ']']) || *body == "end" {
+                    len += 1;
+                }
+                break;
+            }
+            len += 1;
+        }
+        longest = longest.max(len);
+    }
+    longest
+}
+
+/// Smallest non-zero space indentation in the file, clamped to 2–8.
+fn indent_unit(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start_matches(' ').len())
+        .filter(|&n| n > 0)
+        .min()
+        .unwrap_or(4)
+        .clamp(2, 8)
+}
+
+fn indent_level(line: &str, unit: usize) -> usize {
+    let mut tabs = 0;
+    let mut spaces = 0;
+    for c in line.chars() {
+        match c {
+            '\t' => tabs += 1,
+            ' ' => spaces += 1,
+            _ => break,
+        }
+    }
+    tabs + spaces / unit
+}
+
+fn is_comment(text: &str) -> bool {
+    ["//", "#", "/*", "*", "--", "<!--", ";"]
+        .iter()
+        .any(|p| text.starts_with(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST: &str = "// header comment\n\
+                        use std::io;\n\
+                        \n\
+                        fn main() {\n    \
+                            if true {\n        \
+                                println!(\"hi\");\n    \
+                            }\n\
+                        }\n";
+
+    #[test]
+    fn test_measure_counts_code_lines_and_indent() {
+        let m = measure(RUST);
+        assert_eq!(m.loc, 6, "Blank and comment lines should not count");
+        assert_eq!(m.total_indent, 4, "Indent levels: 1 + 2 + 1");
+        assert_eq!(m.max_indent, 2);
+        assert_eq!(
+            m.longest_function, 5,
+            "fn main spans signature to closing brace"
+        );
+    }
+
+    #[test]
+    fn test_python_function_ends_at_dedent() {
+        let py = "def a():\n    x = 1\n    return x\n\ndef b():\n    pass\n";
+        let lines: Vec<(usize, &str)> = py
+            .lines()
+            .map(|l| (indent_level(l, indent_unit(py)), l.trim()))
+            .collect();
+        assert_eq!(
+            longest_function(&lines),
+            4,
+            "def a plus trailing blank line"
+        );
+    }
+
+    #[test]
+    fn test_tabs_count_as_one_level() {
+        assert_eq!(indent_level("\t\tfoo", 4), 2);
+        assert_eq!(indent_level("        foo", 4), 2);
+        assert_eq!(indent_unit("a\n  b\n    c\n"), 2);
+    }
+
+    #[test]
+    fn test_bigger_nested_file_scores_higher() {
+        let small = "x = 1\n".to_string();
+        let big = "def f():\n    if a:\n        for b in c:\n            pass\n".repeat(40);
+        let measured = HashMap::from([
+            ("small.py".to_string(), measure(&small)),
+            ("big.py".to_string(), measure(&big)),
+        ]);
+        let files: Vec<String> = vec!["small.py".into(), "big.py".into(), "gone.py".into()];
+        let result = analyze_complexity(&measured, &files);
+        assert!(
+            result["big.py"].complexity_score > result["small.py"].complexity_score,
+            "Larger, deeper file should score higher"
+        );
+        assert!(result["big.py"].complexity_score <= 100.0);
+        assert_eq!(
+            result["gone.py"].complexity_score, 0.0,
+            "Files missing at HEAD score zero"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::analyzers::bug_correlation::is_bug_fix;
+use crate::analyzers::commit_quality::is_wip;
+use crate::analyzers::revert_tracker::is_revert;
+use crate::types::{AuthorStats, Commit, HotspotResult};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Team for authors matched by no `teams` entry.
+pub const UNASSIGNED_TEAM: &str = "(unassigned)";
+
+/// Inverts the per-file signals into one row per author, or per team when
+/// `teams` is given.
+///
+/// Only commits touching at least one of `files` count. Commit size is the
+/// number of files changed, as for the large-commit signal. A hotspot is
+/// owned by the top author of a file in `hotspots` (the reported results).
+pub fn analyze_contributors(
+    commits: &[Commit],
+    files: &[String],
+    hotspots: &[HotspotResult],
+    teams: Option<&BTreeMap<String, Vec<String>>>,
+) -> Vec<AuthorStats> {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let group_of = |author: &str| -> String {
+        match teams {
+            Some(t) => team_of(author, t).unwrap_or(UNASSIGNED_TEAM).to_string(),
+            None => author.to_string(),
+        }
+    };
+
+    #[derive(Default)]
+    struct Acc<'a> {
+        members: HashSet<&'a str>,
+        commits: usize,
+        files: HashSet<&'a str>,
+        hotspots: usize,
+        bug_fixes: usize,
+        reverts: usize,
+        wip: usize,
+        size: usize,
+    }
+    let mut groups: HashMap<String, Acc> = HashMap::new();
+    for commit in commits {
+        let touched: Vec<&str> = commit
+            .files
+            .iter()
+            .map(String::as_str)
+            .filter(|f| file_set.contains(f))
+            .collect();
+        if touched.is_empty() {
+            continue;
+        }
+        let acc = groups.entry(group_of(&commit.author)).or_default();
+        acc.members.insert(&commit.author);
+        acc.commits += 1;
+        acc.files.extend(touched);
+        acc.bug_fixes += usize::from(is_bug_fix(&commit.subject));
+        acc.reverts += usize::from(is_revert(&commit.subject));
+        acc.wip += usize::from(is_wip(&commit.subject));
+        acc.size += commit.files.len();
+    }
+    for r in hotspots {
+        if let Some(acc) = groups.get_mut(&group_of(&r.details.top_author)) {
+            acc.hotspots += 1;
+        }
+    }
+
+    let mut stats: Vec<AuthorStats> = groups
+        .into_iter()
+        .map(|(name, acc)| {
+            let mut members: Vec<String> = if teams.is_some() {
+                acc.members.iter().map(|m| m.to_string()).collect()
+            } else {
+                Vec::new()
+            };
+            members.sort();
+            AuthorStats {
+                name,
+                members,
+                commits: acc.commits,
+                files_touched: acc.files.len(),
+                hotspots_owned: acc.hotspots,
+                bug_fix_commits: acc.bug_fixes,
+                reverts_authored: acc.reverts,
+                wip_commits: acc.wip,
+                wip_ratio: acc.wip as f64 / acc.commits as f64,
+                avg_commit_size: acc.size as f64 / acc.commits as f64,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    stats
+}
+
+/// Team of `email`: an exact (case-insensitive) member match first, then an
+/// `@domain` entry.
+fn team_of<'a>(email: &str, teams: &'a BTreeMap<String, Vec<String>>) -> Option<&'a str> {
+    let email = email.to_ascii_lowercase();
+    let matching = |exact: bool| {
+        teams.iter().find_map(|(team, members)| {
+            members
+                .iter()
+                .any(|m| {
+                    let m = m.trim().to_ascii_lowercase();
+                    if exact {
+                        m == email
+                    } else {
+                        m.starts_with('@') && email.ends_with(&m)
+                    }
+                })
+                .then_some(team.as_str())
+        })
+    };
+    matching(true).or_else(|| matching(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, subject: &str, files: &[&str]) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            author: author.to_string(),
+            timestamp: 0,
+            subject: subject.to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn commits() -> Vec<Commit> {
+        vec![
+            commit("ann@acme.io", "fix crash in parser", &["a.rs", "b.rs"]),
+            commit("ann@acme.io", "wip", &["a.rs", "Cargo.lock"]),
+            commit("bo@acme.io", "Revert \"add cache\"", &["c.rs"]),
+            commit("cy@other.org", "Add feature flag support", &["c.rs"]),
+            commit("cy@other.org", "Bump lockfile only", &["Cargo.lock"]),
+        ]
+    }
+
+    #[test]
+    fn test_per_author_rows() {
+        let files: Vec<String> = vec!["a.rs".into(), "b.rs".into(), "c.rs".into()];
+        let stats = analyze_contributors(&commits(), &files, &[], None);
+        assert_eq!(stats[0].name, "ann@acme.io");
+        let ann = &stats[0];
+        assert_eq!((ann.commits, ann.files_touched), (2, 2));
+        assert_eq!((ann.bug_fix_commits, ann.wip_commits), (1, 1));
+        assert_eq!(ann.avg_commit_size, 2.0);
+        let cy = stats.iter().find(|s| s.name == "cy@other.org").unwrap();
+        assert_eq!(
+            cy.commits, 1,
+            "Commits touching no analyzed file are skipped"
+        );
+        let bo = stats.iter().find(|s| s.name == "bo@acme.io").unwrap();
+        assert_eq!(bo.reverts_authored, 1);
+    }
+
+    #[test]
+    fn test_team_grouping() {
+        let teams = BTreeMap::from([
+            ("core".to_string(), vec!["@acme.io".to_string()]),
+            ("solo".to_string(), vec!["BO@acme.io".to_string()]),
+        ]);
+        let files: Vec<String> = vec!["a.rs".into(), "b.rs".into(), "c.rs".into()];
+        let stats = analyze_contributors(&commits(), &files, &[], Some(&teams));
+        let names: Vec<&str> = stats.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["core", UNASSIGNED_TEAM, "solo"]);
+        assert_eq!(
+            stats[2].members,
+            vec!["bo@acme.io".to_string()],
+            "Exact email beats domain"
+        );
+    }
+}
@@ -1,12 +1,66 @@
-use crate::types::{Commit, CouplingEntry};
-use std::collections::{HashMap, HashSet};
+use crate::config::BoundaryOverrides;
+use crate::filters::compile_glob;
+use crate::types::{Commit, CoupledFile, CouplingCluster, CouplingEntry};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 // Skip commits touching more than this many files (large merges/reformats)
 const MAX_FILES_PER_COMMIT: usize = 20;
 
+/// Minimum Jaccard strength (%) for a pair to link files into a cluster.
+pub const DEFAULT_CLUSTER_STRENGTH: f64 = 30.0;
+
+// Pairs are already listed on their own; clusters start at three files.
+const MIN_CLUSTER_SIZE: usize = 3;
+
+/// Coupled files listed per reported result.
+pub const MAX_PARTNERS: usize = 10;
+
+/// Strength multiplier in the coupling score for boundary violations.
+const BOUNDARY_VIOLATION_BOOST: f64 = 1.5;
+
 /// Builds a co-change coupling matrix using Jaccard similarity.
 /// Files that always change together suggest hidden dependencies.
 pub fn analyze_coupling(commits: &[Commit], files: &[String]) -> Vec<CouplingEntry> {
+    let (pair_counts, file_counts) = count_pairs(commits, files);
+
+    let mut couplings: Vec<CouplingEntry> = pair_counts
+        .iter()
+        .filter(|(_, &count)| count >= 3)
+        .filter_map(|(key, &co_changes)| {
+            let mut parts = key.splitn(2, "||");
+            let file_a = parts.next()?.to_string();
+            let file_b = parts.next()?.to_string();
+
+            let total_a = *file_counts.get(&file_a).unwrap_or(&1);
+            let total_b = *file_counts.get(&file_b).unwrap_or(&1);
+            let union = total_a + total_b - co_changes;
+            let strength = if union > 0 {
+                (co_changes as f64 / union as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            Some(CouplingEntry {
+                file_a,
+                file_b,
+                co_changes,
+                strength,
+                boundary: None,
+                boundary_allowed: false,
+            })
+        })
+        .collect();
+
+    couplings.sort_by_key(|c| std::cmp::Reverse(c.co_changes));
+    couplings
+}
+
+/// Counts commits per file and per pair (keyed `"a||b"`, sorted) over `files`.
+/// Commits touching more than [`MAX_FILES_PER_COMMIT`] files count per file only.
+fn count_pairs(
+    commits: &[Commit],
+    files: &[String],
+) -> (HashMap<String, usize>, HashMap<String, usize>) {
     let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
 
     let mut pair_counts: HashMap<String, usize> = HashMap::new();
@@ -37,51 +91,286 @@ pub fn analyze_coupling(commits: &[Commit], files: &[String]) -> Vec<CouplingEnt
             }
         }
     }
+    (pair_counts, file_counts)
+}
 
-    let mut couplings: Vec<CouplingEntry> = pair_counts
+/// Up to [`MAX_PARTNERS`] partners of `file` among `couplings`, highest
+/// confidence first. `commit_count` gives any file's number of commits.
+pub fn partners_of(
+    couplings: &[CouplingEntry],
+    file: &str,
+    commit_count: impl Fn(&str) -> usize,
+) -> Vec<CoupledFile> {
+    let own = commit_count(file).max(1);
+    let mut partners: Vec<CoupledFile> = couplings
         .iter()
-        .filter(|(_, &count)| count >= 3)
-        .filter_map(|(key, &co_changes)| {
-            let mut parts = key.splitn(2, "||");
-            let file_a = parts.next()?.to_string();
-            let file_b = parts.next()?.to_string();
-
-            let total_a = *file_counts.get(&file_a).unwrap_or(&1);
-            let total_b = *file_counts.get(&file_b).unwrap_or(&1);
-            let union = total_a + total_b - co_changes;
-            let strength = if union > 0 {
-                (co_changes as f64 / union as f64) * 100.0
+        .filter_map(|c| {
+            let other = if c.file_a == file {
+                &c.file_b
+            } else if c.file_b == file {
+                &c.file_a
             } else {
-                0.0
+                return None;
             };
+            Some(CoupledFile {
+                file: other.clone(),
+                support: c.co_changes,
+                confidence: (c.co_changes as f64 / own as f64 * 100.0).min(100.0),
+                reverse_confidence: (c.co_changes as f64 / commit_count(other).max(1) as f64
+                    * 100.0)
+                    .min(100.0),
+                strength: c.strength,
+            })
+        })
+        .collect();
+    partners.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.support.cmp(&a.support))
+    });
+    partners.truncate(MAX_PARTNERS);
+    partners
+}
 
-            Some(CouplingEntry {
-                file_a,
-                file_b,
-                co_changes,
-                strength,
+/// Merges commits by the same author within `window_hours` of the first
+/// commit of a set into one change set, for work split across commits.
+///
+/// A set only grows while it stays within [`MAX_FILES_PER_COMMIT`] files, so
+/// merging small commits never produces a set whose pairs are skipped; a
+/// commit that would push it over starts a new set. Oversized commits stay
+/// on their own and are skipped for pairs as without a window.
+pub fn group_change_sets(commits: &[Commit], window_hours: u32) -> Vec<Commit> {
+    let window = i64::from(window_hours) * 3600;
+    let mut sorted: Vec<&Commit> = commits.iter().collect();
+    sorted.sort_by(|a, b| a.author.cmp(&b.author).then(a.timestamp.cmp(&b.timestamp)));
+
+    let mut sets: Vec<Commit> = Vec::new();
+    let mut start = 0;
+    for c in sorted {
+        let fits = |set: &Commit| {
+            let added = c.files.iter().filter(|f| !set.files.contains(f)).count();
+            set.files.len() + added <= MAX_FILES_PER_COMMIT
+        };
+        match sets.last_mut() {
+            Some(set) if set.author == c.author && c.timestamp - start <= window && fits(set) => {
+                for f in &c.files {
+                    if !set.files.contains(f) {
+                        set.files.push(f.clone());
+                    }
+                }
+            }
+            _ => {
+                start = c.timestamp;
+                sets.push(c.clone());
+            }
+        }
+    }
+    sets
+}
+
+/// Files that changed together with `target` at least `min_support` times,
+/// ranked by confidence (share of `target`'s change sets that touched them),
+/// then support. Returns the number of change sets touching `target` too.
+pub fn query_coupling(
+    commits: &[Commit],
+    files: &[String],
+    target: &str,
+    min_support: usize,
+) -> (usize, Vec<CoupledFile>) {
+    let (pair_counts, file_counts) = count_pairs(commits, files);
+    let target_count = file_counts.get(target).copied().unwrap_or(0);
+    if target_count == 0 {
+        return (0, Vec::new());
+    }
+
+    let mut coupled: Vec<CoupledFile> = pair_counts
+        .iter()
+        .filter(|(_, &support)| support >= min_support.max(1))
+        .filter_map(|(key, &support)| {
+            let (a, b) = key.split_once("||")?;
+            let other = match (a == target, b == target) {
+                (true, _) => b,
+                (_, true) => a,
+                _ => return None,
+            };
+            let other_count = file_counts.get(other).copied().unwrap_or(1);
+            let union = target_count + other_count - support;
+            Some(CoupledFile {
+                file: other.to_string(),
+                support,
+                confidence: support as f64 / target_count as f64 * 100.0,
+                reverse_confidence: support as f64 / other_count as f64 * 100.0,
+                strength: support as f64 / union as f64 * 100.0,
             })
         })
         .collect();
 
-    couplings.sort_by(|a, b| b.co_changes.cmp(&a.co_changes));
-    couplings
+    coupled.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.support.cmp(&a.support))
+            .then(a.file.cmp(&b.file))
+    });
+    (target_count, coupled)
+}
+
+/// Marks pairs whose files belong to different configured components, and
+/// whether `allow` declares that dependency.
+pub fn classify_boundaries(couplings: &mut [CouplingEntry], boundaries: &BoundaryOverrides) {
+    if boundaries.components.is_empty() {
+        return;
+    }
+    // Globs are validated on load, so any that fail to compile here are skipped.
+    let components: Vec<(&str, Vec<regex::Regex>)> = boundaries
+        .components
+        .iter()
+        .map(|c| {
+            let globs = c
+                .paths
+                .iter()
+                .filter_map(|p| compile_glob(p).ok())
+                .collect();
+            (c.name.as_str(), globs)
+        })
+        .collect();
+    let component_of = |file: &str| {
+        components
+            .iter()
+            .find(|(_, globs)| globs.iter().any(|g| g.is_match(file)))
+            .map(|(name, _)| *name)
+    };
+    let allowed = |a: &str, b: &str| {
+        boundaries.allow.iter().any(|(from, to)| {
+            let matches = |side: &str, name: &str| side == "*" || side == name;
+            (matches(from, a) && matches(to, b)) || (matches(from, b) && matches(to, a))
+        })
+    };
+
+    for c in couplings.iter_mut() {
+        let (Some(a), Some(b)) = (component_of(&c.file_a), component_of(&c.file_b)) else {
+            continue;
+        };
+        if a != b {
+            c.boundary = Some(format!("{a} ↔ {b}"));
+            c.boundary_allowed = allowed(a, b);
+        }
+    }
 }
 
 /// Returns a per-file coupling score: each file's maximum Jaccard strength.
+/// Boundary violations count 1.5× their strength, capped at 100.
 pub fn get_coupling_scores(files: &[String], couplings: &[CouplingEntry]) -> HashMap<String, f64> {
     let mut scores: HashMap<String, f64> = files.iter().map(|f| (f.clone(), 0.0)).collect();
     for c in couplings {
+        let strength = if c.is_boundary_violation() {
+            (c.strength * BOUNDARY_VIOLATION_BOOST).min(100.0)
+        } else {
+            c.strength
+        };
         scores
             .entry(c.file_a.clone())
-            .and_modify(|s| *s = s.max(c.strength));
+            .and_modify(|s| *s = s.max(strength));
         scores
             .entry(c.file_b.clone())
-            .and_modify(|s| *s = s.max(c.strength));
+            .and_modify(|s| *s = s.max(strength));
     }
     scores
 }
 
+/// Groups files into clusters: connected components of the coupling graph
+/// keeping only links with `strength >= min_strength`. Largest first.
+pub fn find_clusters(couplings: &[CouplingEntry], min_strength: f64) -> Vec<CouplingCluster> {
+    let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for c in couplings.iter().filter(|c| c.strength >= min_strength) {
+        graph.entry(&c.file_a).or_default().push(&c.file_b);
+        graph.entry(&c.file_b).or_default().push(&c.file_a);
+    }
+    // Every detected pair, so cohesion also counts weaker internal links.
+    let strengths: HashMap<(&str, &str), f64> = couplings
+        .iter()
+        .map(|c| ((c.file_a.as_str(), c.file_b.as_str()), c.strength))
+        .collect();
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut clusters = Vec::new();
+    for &start in graph.keys() {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut members = vec![start];
+        let mut i = 0;
+        while i < members.len() {
+            for &next in &graph[members[i]] {
+                if seen.insert(next) {
+                    members.push(next);
+                }
+            }
+            i += 1;
+        }
+        if members.len() < MIN_CLUSTER_SIZE {
+            continue;
+        }
+        members.sort_by(|a, b| graph[b].len().cmp(&graph[a].len()).then(a.cmp(b)));
+
+        let mut linked = 0.0;
+        for (i, a) in members.iter().enumerate() {
+            for b in &members[i + 1..] {
+                let key = if a < b { (*a, *b) } else { (*b, *a) };
+                linked += strengths.get(&key).copied().unwrap_or(0.0);
+            }
+        }
+        let pairs = members.len() * (members.len() - 1) / 2;
+
+        let directories: BTreeSet<&str> = members.iter().map(|f| parent_dir(f)).collect();
+        let components: BTreeSet<&str> = members
+            .iter()
+            .map(|f| f.split_once('/').map_or(".", |(top, _)| top))
+            .collect();
+        let common = common_dir(&directories);
+        clusters.push(CouplingCluster {
+            name: if common.is_empty() {
+                members[0].to_string()
+            } else {
+                format!("{common}/")
+            },
+            files: members.iter().map(|f| f.to_string()).collect(),
+            cohesion: linked / pairs as f64,
+            directories: directories.iter().map(|d| d.to_string()).collect(),
+            cross_component: components.len() > 1,
+        });
+    }
+
+    clusters.sort_by(|a, b| {
+        b.files.len().cmp(&a.files.len()).then(
+            b.cohesion
+                .partial_cmp(&a.cohesion)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+    clusters
+}
+
+fn parent_dir(file: &str) -> &str {
+    file.rsplit_once('/').map_or(".", |(dir, _)| dir)
+}
+
+/// Longest directory prefix (whole path components) shared by `dirs`.
+fn common_dir<'a>(dirs: &BTreeSet<&'a str>) -> &'a str {
+    let mut iter = dirs.iter();
+    let Some(&first) = iter.next() else {
+        return "";
+    };
+    let mut common = if first == "." { "" } else { first };
+    for &dir in iter {
+        while !common.is_empty() && dir != common && !dir.starts_with(&format!("{common}/")) {
+            common = common.rsplit_once('/').map_or("", |(up, _)| up);
+        }
+    }
+    common
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,4 +478,180 @@ mod tests {
             "Commits with > 20 watched files should be excluded from coupling analysis"
         );
     }
+
+    fn entry(a: &str, b: &str, strength: f64) -> CouplingEntry {
+        CouplingEntry {
+            file_a: a.to_string(),
+            file_b: b.to_string(),
+            co_changes: 5,
+            strength,
+            boundary: None,
+            boundary_allowed: false,
+        }
+    }
+
+    #[test]
+    fn test_clusters_from_connected_components() {
+        let couplings = vec![
+            entry("src/pay/api.rs", "src/pay/db.rs", 80.0),
+            entry("src/pay/db.rs", "src/pay/model.rs", 60.0),
+            entry("src/pay/api.rs", "src/pay/model.rs", 10.0),
+            entry("src/ui/a.rs", "web/b.ts", 50.0),
+            entry("web/b.ts", "web/c.ts", 50.0),
+            // Below the threshold: x/y stay out, and no pair-only cluster.
+            entry("src/pay/model.rs", "x.rs", 20.0),
+            entry("p.rs", "q.rs", 90.0),
+        ];
+        let clusters = find_clusters(&couplings, 30.0);
+        assert_eq!(clusters.len(), 2);
+
+        let pay = clusters.iter().find(|c| c.name == "src/pay/").unwrap();
+        assert_eq!(pay.files[0], "src/pay/db.rs", "Best-connected file first");
+        assert_eq!(pay.files.len(), 3);
+        assert!((pay.cohesion - 50.0).abs() < 1e-9, "(80+60+10)/3 pairs");
+        assert!(!pay.cross_component);
+
+        let mixed = clusters.iter().find(|c| c.name == "web/b.ts").unwrap();
+        assert!(mixed.cross_component);
+        assert_eq!(mixed.directories, vec!["src/ui", "web"]);
+    }
+
+    #[test]
+    fn test_boundary_violations_classified_and_boosted() {
+        use crate::config::BoundaryComponent;
+        let component = |name: &str, glob: &str| BoundaryComponent {
+            name: name.to_string(),
+            paths: vec![glob.to_string()],
+        };
+        let boundaries = BoundaryOverrides {
+            components: vec![
+                component("web", "frontend/**"),
+                component("billing", "billing-service/**"),
+                component("shared", "libs/**"),
+            ],
+            allow: vec![("*".to_string(), "shared".to_string())],
+        };
+        let mut couplings = vec![
+            entry("frontend/a.ts", "frontend/b.ts", 40.0),
+            entry("frontend/a.ts", "billing-service/pay.go", 40.0),
+            entry("libs/util.rs", "billing-service/pay.go", 40.0),
+            entry("README.md", "frontend/a.ts", 40.0),
+        ];
+        classify_boundaries(&mut couplings, &boundaries);
+
+        assert_eq!(couplings[0].boundary, None, "Same component");
+        assert_eq!(couplings[1].boundary.as_deref(), Some("web ↔ billing"));
+        assert!(couplings[1].is_boundary_violation());
+        assert!(couplings[2].boundary.is_some() && couplings[2].boundary_allowed);
+        assert!(
+            !couplings[2].is_boundary_violation(),
+            "Allowed either way round"
+        );
+        assert_eq!(couplings[3].boundary, None, "Unassigned files never cross");
+
+        let files: Vec<String> = vec!["frontend/b.ts".into(), "billing-service/pay.go".into()];
+        let scores = get_coupling_scores(&files, &couplings);
+        assert_eq!(scores["frontend/b.ts"], 40.0);
+        assert_eq!(scores["billing-service/pay.go"], 60.0);
+    }
+
+    fn authored(author: &str, hours: i64, files: &[&str]) -> Commit {
+        Commit {
+            author: author.to_string(),
+            timestamp: 1700000000 + hours * 3600,
+            ..make_commit(files)
+        }
+    }
+
+    #[test]
+    fn test_query_ranks_by_confidence() {
+        // a.rs changes 4 times: always with b.rs, twice with c.rs.
+        // c.rs changes 2 times, both with a.rs.
+        let commits = vec![
+            make_commit(&["a.rs", "b.rs", "c.rs"]),
+            make_commit(&["a.rs", "b.rs", "c.rs"]),
+            make_commit(&["a.rs", "b.rs"]),
+            make_commit(&["a.rs", "b.rs"]),
+            make_commit(&["b.rs"]),
+            make_commit(&["b.rs", "d.rs"]),
+        ];
+        let files: Vec<String> = ["a.rs", "b.rs", "c.rs", "d.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (change_sets, coupled) = query_coupling(&commits, &files, "a.rs", 2);
+        assert_eq!(change_sets, 4);
+        let names: Vec<&str> = coupled.iter().map(|c| c.file.as_str()).collect();
+        assert_eq!(names, vec!["b.rs", "c.rs"], "d.rs never changed with a.rs");
+        assert_eq!(coupled[0].confidence, 100.0);
+        assert_eq!(coupled[1].confidence, 50.0);
+        assert_eq!(coupled[1].reverse_confidence, 100.0, "c.rs → a.rs always");
+
+        assert_eq!(query_coupling(&commits, &files, "x.rs", 1).0, 0);
+    }
+
+    #[test]
+    fn test_change_sets_merge_same_author_within_window() {
+        let commits = vec![
+            authored("ann", 0, &["a.rs"]),
+            authored("ann", 2, &["b.rs"]),
+            authored("bo", 1, &["c.rs"]),
+            authored("ann", 5, &["c.rs"]),
+        ];
+        let sets = group_change_sets(&commits, 4);
+        assert_eq!(sets.len(), 3);
+        let ann: Vec<&Commit> = sets.iter().filter(|s| s.author == "ann").collect();
+        assert_eq!(ann[0].files, vec!["a.rs", "b.rs"]);
+        assert_eq!(
+            ann[1].files,
+            vec!["c.rs"],
+            "Window counts from the set's first commit"
+        );
+    }
+
+    #[test]
+    fn test_change_sets_stay_within_pair_limit() {
+        let batch = |n: usize| -> Vec<String> { (0..8).map(|i| format!("f{n}_{i}.rs")).collect() };
+        let (a, b, c) = (batch(0), batch(1), batch(2));
+        let big: Vec<String> = (0..25).map(|i| format!("big{i}.rs")).collect();
+        let commits: Vec<Commit> = [&a, &b, &c, &big]
+            .iter()
+            .enumerate()
+            .map(|(hour, files)| Commit {
+                files: files.to_vec(),
+                ..authored("ann", hour as i64, &[])
+            })
+            .collect();
+        let sets = group_change_sets(&commits, 4);
+        let sizes: Vec<usize> = sets.iter().map(|s| s.files.len()).collect();
+        assert_eq!(sizes, vec![16, 8, 25], "Merging stops before 20 files");
+
+        let files: Vec<String> = [a, b, c, big].concat();
+        let (_, coupled) = query_coupling(&sets, &files, "f2_0.rs", 1);
+        assert_eq!(coupled.len(), 7, "Pairs of the third commit are kept");
+        let (_, coupled) = query_coupling(&sets, &files, "f0_0.rs", 1);
+        assert_eq!(coupled.len(), 15, "Merged pairs span both commits");
+        let (_, coupled) = query_coupling(&sets, &files, "big0.rs", 1);
+        assert!(
+            coupled.is_empty(),
+            "Oversized commits still count per file only"
+        );
+    }
+
+    #[test]
+    fn test_partners_of_either_side_of_pair() {
+        let mut couplings = vec![
+            entry("a.rs", "b.rs", 50.0),
+            entry("c.rs", "a.rs", 80.0),
+            entry("b.rs", "c.rs", 90.0),
+        ];
+        couplings[1].co_changes = 8;
+        let counts = HashMap::from([("a.rs", 10), ("b.rs", 5), ("c.rs", 16)]);
+        let partners = partners_of(&couplings, "a.rs", |f| counts[f]);
+        let files: Vec<&str> = partners.iter().map(|p| p.file.as_str()).collect();
+        assert_eq!(files, vec!["c.rs", "b.rs"]);
+        assert_eq!(partners[0].confidence, 80.0);
+        assert_eq!(partners[0].reverse_confidence, 50.0);
+        assert_eq!(partners[1].reverse_confidence, 100.0);
+    }
 }
@@ -0,0 +1,306 @@
+use crate::types::CoverageData;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Per-file line hits as read from a report: path → (line number → hits).
+type LineHits = HashMap<String, HashMap<u32, u64>>;
+
+/// Reads an LCOV, Cobertura or JaCoCo XML report and returns line coverage
+/// for each of `files` the report mentions.
+///
+/// Report paths are often absolute, or relative to a source root rather than
+/// the repository, so a report path matches a file when either is a
+/// path-suffix of the other; the longest match wins. A report path whose
+/// longest match is shared by several files (`util.py` against `a/util.py`
+/// and `b/util.py`) cannot be attributed and is skipped.
+pub fn analyze_coverage(
+    report: &Path,
+    files: &[String],
+) -> Result<HashMap<String, CoverageData>, String> {
+    let content = std::fs::read_to_string(report)
+        .map_err(|e| format!("Cannot read coverage report '{}': {e}", report.display()))?;
+    let entries = parse_report(&content)
+        .map_err(|e| format!("Invalid coverage report '{}': {e}", report.display()))?;
+    Ok(match_files(&entries, files))
+}
+
+/// Detects the format from content and returns `(covered, total)` lines per path.
+fn parse_report(content: &str) -> Result<HashMap<String, (usize, usize)>, String> {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with('<') {
+        return Ok(parse_lcov(content));
+    }
+    let doc = roxmltree::Document::parse_with_options(
+        content,
+        roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    let root = doc.root_element();
+    match root.tag_name().name() {
+        "coverage" => Ok(totals(parse_cobertura(root))),
+        "report" => Ok(parse_jacoco(root)),
+        other => Err(format!(
+            "unrecognized root element <{other}>; expected LCOV, Cobertura <coverage> or JaCoCo <report>"
+        )),
+    }
+}
+
+/// `SF:` starts a file, `DA:line,hits` records a line, `end_of_record` closes it.
+/// A file listed under several test names (`TN:`) is merged.
+fn parse_lcov(content: &str) -> HashMap<String, (usize, usize)> {
+    let mut hits: LineHits = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(path.to_string());
+            hits.entry(path.to_string()).or_default();
+        } else if let Some(da) = line.strip_prefix("DA:") {
+            let mut parts = da.split(',');
+            let (Some(file), Some(n), Some(h)) = (current.as_ref(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if let (Ok(n), Ok(h)) = (n.parse::<u32>(), h.parse::<u64>()) {
+                let lines = hits.entry(file.clone()).or_default();
+                let e = lines.entry(n).or_insert(0);
+                *e = (*e).max(h);
+            }
+        } else if line == "end_of_record" {
+            current = None;
+        }
+    }
+    totals(hits)
+}
+
+/// Reads `class/lines/line` elements. Paths are joined to the `<source>`
+/// root when the report has exactly one.
+fn parse_cobertura(root: roxmltree::Node) -> LineHits {
+    let sources: Vec<&str> = root
+        .descendants()
+        .filter(|n| n.has_tag_name("source"))
+        .filter_map(|n| n.text())
+        .map(str::trim)
+        .collect();
+    let base = match sources.as_slice() {
+        [single] => Some(single.trim_end_matches(['/', '\\'])),
+        _ => None,
+    };
+
+    let mut hits: LineHits = HashMap::new();
+    for class in root.descendants().filter(|n| n.has_tag_name("class")) {
+        let Some(filename) = class.attribute("filename") else {
+            continue;
+        };
+        let path = match base {
+            Some(b) if !b.is_empty() && !filename.starts_with('/') => format!("{b}/{filename}"),
+            _ => filename.to_string(),
+        };
+        let lines = hits.entry(path).or_default();
+        // Only direct <lines>; <methods> repeat the same lines.
+        let class_lines = class
+            .children()
+            .filter(|n| n.has_tag_name("lines"))
+            .flat_map(|n| n.children().filter(|l| l.has_tag_name("line")));
+        for line in class_lines {
+            let n = line.attribute("number").and_then(|v| v.parse::<u32>().ok());
+            let h = line.attribute("hits").and_then(|v| v.parse::<u64>().ok());
+            if let (Some(n), Some(h)) = (n, h) {
+                let e = lines.entry(n).or_insert(0);
+                *e = (*e).max(h);
+            }
+        }
+    }
+    hits
+}
+
+/// Uses each `sourcefile`'s LINE counter; the path is `package/name`.
+fn parse_jacoco(root: roxmltree::Node) -> HashMap<String, (usize, usize)> {
+    let mut out = HashMap::new();
+    for package in root.descendants().filter(|n| n.has_tag_name("package")) {
+        let pkg = package.attribute("name").unwrap_or("");
+        for source in package.children().filter(|n| n.has_tag_name("sourcefile")) {
+            let Some(name) = source.attribute("name") else {
+                continue;
+            };
+            let Some(counter) = source
+                .children()
+                .find(|n| n.has_tag_name("counter") && n.attribute("type") == Some("LINE"))
+            else {
+                continue;
+            };
+            let count = |attr| {
+                counter
+                    .attribute(attr)
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(0)
+            };
+            let (missed, covered) = (count("missed"), count("covered"));
+            let path = if pkg.is_empty() {
+                name.to_string()
+            } else {
+                format!("{pkg}/{name}")
+            };
+            out.insert(path, (covered, covered + missed));
+        }
+    }
+    out
+}
+
+fn totals(hits: LineHits) -> HashMap<String, (usize, usize)> {
+    hits.into_iter()
+        .map(|(path, lines)| {
+            let covered = lines.values().filter(|&&h| h > 0).count();
+            (path, (covered, lines.len()))
+        })
+        .collect()
+}
+
+fn match_files(
+    entries: &HashMap<String, (usize, usize)>,
+    files: &[String],
+) -> HashMap<String, CoverageData> {
+    let mut normalized: Vec<(String, (usize, usize))> = entries
+        .iter()
+        .filter(|(_, (_, total))| *total > 0)
+        .map(|(p, &counts)| {
+            let p = p.replace('\\', "/");
+            (p.trim_start_matches("./").to_string(), counts)
+        })
+        .collect();
+    // Sorted so equally long matches resolve the same way on every run.
+    normalized.sort();
+
+    // file → (matched length, counts); on equal length the first path wins.
+    let mut best: HashMap<&str, (usize, (usize, usize))> = HashMap::new();
+    for (path, counts) in &normalized {
+        let matches: Vec<(usize, &str)> = files
+            .iter()
+            .filter(|f| is_path_suffix(path, f) || is_path_suffix(f, path))
+            .map(|f| (path.len().min(f.len()), f.as_str()))
+            .collect();
+        let Some(len) = matches.iter().map(|(len, _)| *len).max() else {
+            continue;
+        };
+        let mut longest = matches.iter().filter(|(l, _)| *l == len);
+        let (Some(&(_, file)), None) = (longest.next(), longest.next()) else {
+            continue;
+        };
+        match best.get(file) {
+            Some(&(held, _)) if held >= len => {}
+            _ => {
+                best.insert(file, (len, *counts));
+            }
+        }
+    }
+
+    best.into_iter()
+        .map(|(file, (_, (covered, total)))| {
+            let data = CoverageData {
+                lines_covered: covered,
+                lines_total: total,
+                line_coverage: covered as f64 / total as f64 * 100.0,
+            };
+            (file.to_string(), data)
+        })
+        .collect()
+}
+
+/// True when `suffix` equals `path` or ends it at a `/` boundary.
+fn is_path_suffix(path: &str, suffix: &str) -> bool {
+    path == suffix || (path.ends_with(suffix) && path[..path.len() - suffix.len()].ends_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_lcov_merges_test_names_and_matches_absolute_paths() {
+        let lcov =
+            "TN:unit\nSF:/ci/build/src/app.rs\nDA:1,1\nDA:2,0\nDA:3,0\nDA:4,0\nend_of_record\n\
+                    TN:integration\nSF:/ci/build/src/app.rs\nDA:2,5\nend_of_record\n";
+        let entries = parse_report(lcov).unwrap();
+        let result = match_files(&entries, &files(&["src/app.rs", "src/other.rs"]));
+        let app = &result["src/app.rs"];
+        assert_eq!((app.lines_covered, app.lines_total), (2, 4));
+        assert!((app.line_coverage - 50.0).abs() < 1e-9);
+        assert!(
+            !result.contains_key("src/other.rs"),
+            "Files missing from the report have no coverage"
+        );
+    }
+
+    #[test]
+    fn test_cobertura_joins_single_source_root() {
+        let xml = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.5">
+  <sources><source>/home/ci/repo/pkg</source></sources>
+  <packages><package name="pkg"><classes>
+    <class name="util" filename="util.py">
+      <methods><method name="f"><lines><line number="1" hits="1"/></lines></method></methods>
+      <lines><line number="1" hits="1"/><line number="2" hits="0"/><line number="3" hits="0"/></lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+        let entries = parse_report(xml).unwrap();
+        assert_eq!(entries["/home/ci/repo/pkg/util.py"], (1, 3));
+        let result = match_files(&entries, &files(&["pkg/util.py", "other/util.py"]));
+        assert!(result.contains_key("pkg/util.py"));
+        assert!(
+            !result.contains_key("other/util.py"),
+            "Suffix match must respect directories"
+        );
+    }
+
+    #[test]
+    fn test_jacoco_package_path_matches_maven_layout() {
+        let xml = r#"<report name="demo">
+  <package name="com/acme">
+    <sourcefile name="Billing.java">
+      <counter type="INSTRUCTION" missed="50" covered="50"/>
+      <counter type="LINE" missed="30" covered="10"/>
+    </sourcefile>
+  </package>
+</report>"#;
+        let entries = parse_report(xml).unwrap();
+        let result = match_files(
+            &entries,
+            &files(&["service/src/main/java/com/acme/Billing.java"]),
+        );
+        let b = &result["service/src/main/java/com/acme/Billing.java"];
+        assert_eq!((b.lines_covered, b.lines_total), (10, 40));
+    }
+
+    #[test]
+    fn test_ambiguous_report_path_is_skipped() {
+        let entries = HashMap::from([
+            ("util.py".to_string(), (1, 2)),
+            ("/ci/repo/src/app.rs".to_string(), (3, 4)),
+            ("repo/src/app.rs".to_string(), (1, 4)),
+            ("src/app.rs".to_string(), (2, 4)),
+        ]);
+        let result = match_files(&entries, &files(&["a/util.py", "b/util.py", "src/app.rs"]));
+        assert!(
+            !result.contains_key("a/util.py") && !result.contains_key("b/util.py"),
+            "util.py could be either file"
+        );
+        assert_eq!(
+            result["src/app.rs"].lines_covered, 3,
+            "Equal matches resolve to the first report path"
+        );
+    }
+
+    #[test]
+    fn test_unknown_xml_is_an_error() {
+        assert!(parse_report("<html></html>").is_err());
+    }
+}
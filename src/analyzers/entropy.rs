@@ -0,0 +1,146 @@
+use crate::types::{Commit, EntropyData, EntropyPeriod};
+use std::collections::{HashMap, HashSet};
+
+/// Length of one entropy period.
+const PERIOD_SECS: i64 = 30 * 86400;
+
+/// Computes Hassan's entropy of changes: for each 30-day period, the
+/// normalized Shannon entropy of how that period's changes spread across
+/// files. High entropy means scattered, unfocused change.
+///
+/// Each file is credited with `p × H` for every period it changed in, where
+/// `p` is its share of the period's changes and `H` the period entropy
+/// (Hassan's HCPF2), summed over all periods.
+pub fn analyze_entropy(
+    commits: &[Commit],
+    files: &[String],
+) -> (HashMap<String, EntropyData>, Vec<EntropyPeriod>) {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let start = commits.iter().map(|c| c.timestamp).min().unwrap_or(0);
+
+    // period index → (commit count, file → changes)
+    let mut periods: HashMap<i64, (usize, HashMap<&str, usize>)> = HashMap::new();
+    for commit in commits {
+        let touched: Vec<&str> = commit
+            .files
+            .iter()
+            .map(String::as_str)
+            .filter(|f| file_set.contains(f))
+            .collect();
+        if touched.is_empty() {
+            continue;
+        }
+        let period = periods
+            .entry((commit.timestamp - start) / PERIOD_SECS)
+            .or_default();
+        period.0 += 1;
+        for f in touched {
+            *period.1.entry(f).or_insert(0) += 1;
+        }
+    }
+
+    let mut history: HashMap<&str, f64> = HashMap::new();
+    let mut series: Vec<EntropyPeriod> = Vec::new();
+    for (index, (commit_count, changes)) in &periods {
+        let entropy = normalized_entropy(changes.values().copied());
+        let total: usize = changes.values().sum();
+        for (file, n) in changes {
+            *history.entry(file).or_insert(0.0) += *n as f64 / total as f64 * entropy;
+        }
+        let period_start = start + index * PERIOD_SECS;
+        series.push(EntropyPeriod {
+            start: chrono::DateTime::from_timestamp(period_start, 0)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            commit_count: *commit_count,
+            file_count: changes.len(),
+            entropy,
+        });
+    }
+    series.sort_by(|a, b| a.start.cmp(&b.start));
+
+    let max = history.values().copied().fold(0.0001_f64, f64::max);
+    let data = files
+        .iter()
+        .map(|file| {
+            let change_entropy = history.get(file.as_str()).copied().unwrap_or(0.0);
+            let data = EntropyData {
+                change_entropy,
+                entropy_score: change_entropy / max * 100.0,
+            };
+            (file.clone(), data)
+        })
+        .collect();
+    (data, series)
+}
+
+/// Shannon entropy of the change distribution divided by its maximum,
+/// `log2(n)`, so periods with different file counts compare on 0–1.
+fn normalized_entropy(counts: impl Iterator<Item = usize>) -> f64 {
+    let counts: Vec<usize> = counts.filter(|&c| c > 0).collect();
+    if counts.len() < 2 {
+        return 0.0;
+    }
+    let total = counts.iter().sum::<usize>() as f64;
+    let h: f64 = counts
+        .iter()
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum();
+    h / (counts.len() as f64).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(ts: i64, files: &[&str]) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            author: "dev".to_string(),
+            timestamp: ts,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_normalized_entropy_bounds() {
+        assert_eq!(
+            normalized_entropy([5].into_iter()),
+            0.0,
+            "One file: no spread"
+        );
+        assert!((normalized_entropy([3, 3, 3].into_iter()) - 1.0).abs() < 1e-9);
+        let skewed = normalized_entropy([9, 1].into_iter());
+        assert!(skewed > 0.0 && skewed < 1.0);
+    }
+
+    #[test]
+    fn test_scattered_period_outweighs_focused_one() {
+        let day = 86400;
+        let files: Vec<String> = ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs", "f.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let commits = vec![
+            // Period 0: focused on a.rs
+            commit(0, &["a.rs"]),
+            commit(day, &["a.rs"]),
+            commit(2 * day, &["a.rs", "b.rs"]),
+            // Period 1: spread evenly over four files
+            commit(40 * day, &["c.rs", "d.rs"]),
+            commit(41 * day, &["e.rs", "f.rs"]),
+        ];
+        let (data, periods) = analyze_entropy(&commits, &files);
+        assert_eq!(periods.len(), 2);
+        assert!(periods[1].entropy > periods[0].entropy);
+        assert!(
+            data["c.rs"].entropy_score > data["b.rs"].entropy_score,
+            "Same share of changes, but in a more chaotic period"
+        );
+        assert!((data["a.rs"].entropy_score - 100.0).abs() < 1e-9);
+    }
+}
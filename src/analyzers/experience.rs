@@ -0,0 +1,114 @@
+use crate::types::{Commit, ExperienceData};
+use std::collections::{HashMap, HashSet};
+
+/// Authors with fewer prior commits than this in the repository are newcomers.
+const NEWCOMER_COMMITS: usize = 10;
+
+/// Replays history oldest-first and, for every change to a file, counts the
+/// author's earlier commits to that file and to the repository.
+///
+/// A change is low-experience when its author is a newcomer to the
+/// repository, or edits a file that already had history without ever having
+/// touched it before. Creating a file is not unfamiliar. Counts only see the
+/// analyzed history, so with `--since` long-time authors start from zero.
+pub fn analyze_experience(commits: &[Commit], files: &[String]) -> HashMap<String, ExperienceData> {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+
+    let mut order: Vec<&Commit> = commits.iter().collect();
+    order.sort_by_key(|c| c.timestamp);
+
+    let mut repo_commits: HashMap<&str, usize> = HashMap::new();
+    let mut file_commits: HashMap<(&str, &str), usize> = HashMap::new();
+    let mut file_changes: HashMap<&str, usize> = HashMap::new();
+    // filename → (changes, low-experience changes, Σ prior file commits, Σ prior repo commits)
+    let mut totals: HashMap<&str, (usize, usize, usize, usize)> = HashMap::new();
+
+    for commit in order {
+        let author = commit.author.as_str();
+        let prior_repo = repo_commits.get(author).copied().unwrap_or(0);
+        for file in &commit.files {
+            let file = file.as_str();
+            if !file_set.contains(file) {
+                continue;
+            }
+            let prior_file = file_commits.get(&(author, file)).copied().unwrap_or(0);
+            let existing = file_changes.get(file).copied().unwrap_or(0) > 0;
+            let low = prior_repo < NEWCOMER_COMMITS || (existing && prior_file == 0);
+
+            let t = totals.entry(file).or_insert((0, 0, 0, 0));
+            t.0 += 1;
+            t.1 += usize::from(low);
+            t.2 += prior_file;
+            t.3 += prior_repo;
+
+            *file_commits.entry((author, file)).or_insert(0) += 1;
+            *file_changes.entry(file).or_insert(0) += 1;
+        }
+        *repo_commits.entry(author).or_insert(0) += 1;
+    }
+
+    totals
+        .into_iter()
+        .map(|(file, (changes, low, file_sum, repo_sum))| {
+            let share = low as f64 / changes as f64;
+            let data = ExperienceData {
+                low_experience_changes: low,
+                avg_prior_file_commits: file_sum as f64 / changes as f64,
+                avg_prior_repo_commits: repo_sum as f64 / changes as f64,
+                experience_score: share * 100.0,
+            };
+            (file.to_string(), data)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, ts: i64, files: &[&str]) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            author: author.to_string(),
+            timestamp: ts,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_newcomers_and_unfamiliar_files_count_as_low_experience() {
+        // Veteran builds up history first; newest commits come first, as from git log.
+        let mut commits: Vec<Commit> = (0..12).map(|i| commit("vet", i, &["core.rs"])).collect();
+        commits.push(commit("vet", 20, &["util.rs"]));
+        commits.push(commit("new", 30, &["core.rs"]));
+        commits.reverse();
+        let files: Vec<String> = vec!["core.rs".into(), "util.rs".into()];
+        let data = analyze_experience(&commits, &files);
+
+        let core = &data["core.rs"];
+        // The veteran's first ten commits were still as a newcomer, plus the newcomer's one.
+        assert_eq!(core.low_experience_changes, 11);
+        assert!((core.experience_score - 11.0 / 13.0 * 100.0).abs() < 1e-9);
+
+        let util = &data["util.rs"];
+        assert_eq!(
+            util.low_experience_changes, 0,
+            "Creating a file is not an unfamiliar edit"
+        );
+        assert_eq!(util.avg_prior_repo_commits, 12.0);
+    }
+
+    #[test]
+    fn test_experienced_author_in_someone_elses_file() {
+        let mut commits: Vec<Commit> = (0..10).map(|i| commit("a", i, &["a.rs"])).collect();
+        commits.extend((10..20).map(|i| commit("b", i, &["b.rs"])));
+        commits.push(commit("a", 30, &["b.rs"]));
+        commits.push(commit("a", 31, &["b.rs"]));
+        let files: Vec<String> = vec!["b.rs".into()];
+        let b = &analyze_experience(&commits, &files)["b.rs"];
+        // b's ten newcomer commits, then a's first (unfamiliar) edit; a's second is familiar.
+        assert_eq!(b.low_experience_changes, 11);
+        assert!((b.avg_prior_file_commits - 46.0 / 12.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,294 @@
+use super::bug_correlation::is_bug_fix;
+use super::churn::DECAY_LAMBDA;
+use super::revert_tracker::is_revert;
+use crate::git::patch_parser::{for_each_hunk, Hunk};
+use crate::types::{FunctionHotspot, Weights};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Maps file extensions to git diff drivers. Rust, Go, Python and Java use
+/// git's built-in function patterns; JavaScript/TypeScript use our own.
+const ATTRIBUTES: &str = "\
+*.rs diff=rust
+*.go diff=golang
+*.py diff=python
+*.java diff=java
+*.js diff=scanline-js
+*.jsx diff=scanline-js
+*.mjs diff=scanline-js
+*.ts diff=scanline-js
+*.tsx diff=scanline-js
+";
+
+/// POSIX ERE lines for `diff.scanline-js.xfuncname`; `!` lines reject a match.
+const JS_XFUNCNAME: &str = concat!(
+    "!^[ \t]*(if|for|while|switch|catch|return|else)[ \t(]\n",
+    "^[ \t]*((export[ \t]+)?(default[ \t]+)?(async[ \t]+)?function[ \t*]*[A-Za-z_$][A-Za-z0-9_$]*.*)$\n",
+    "^[ \t]*((export[ \t]+)?(const|let|var)[ \t]+[A-Za-z_$][A-Za-z0-9_$]*[ \t]*=[ \t]*(async[ \t]*)?(\\([^)]*\\)|[A-Za-z_$][A-Za-z0-9_$]*)[ \t]*=>.*)$\n",
+    "^[ \t]*(((public|private|protected|static|async|readonly|get|set|override)[ \t]+)*[A-Za-z_$][A-Za-z0-9_$]*[ \t]*\\(.*\\)[ \t]*(:[^={]*)?\\{[ \t]*)$\n",
+    "^[ \t]*((export[ \t]+)?(default[ \t]+)?(abstract[ \t]+)?class[ \t]+.*)$",
+);
+
+static FN_KEYWORD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:fn|def|func|function\*?|fun)\s+(?:\([^)]*\)\s*)?([A-Za-z_$][\w$]*)")
+        .expect("fn keyword regex")
+});
+static ARROW_BINDING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=").expect("arrow binding regex")
+});
+static CLASS_DECL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:class|struct|interface|trait)\s+([A-Za-z_$][\w$]*)").expect("class regex")
+});
+static CALL_LIKE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Za-z_$][\w$]*)\s*\(").expect("call-like regex"));
+
+/// Words that can precede `(` on a hunk context line without naming a function.
+const NOT_NAMES: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "match", "new", "await", "typeof",
+];
+
+#[derive(Default)]
+struct Acc {
+    last_commit: String,
+    commits: usize,
+    weighted: f64,
+    bugs: usize,
+    reverts: usize,
+}
+
+/// Attributes each changed hunk to its enclosing function using the context
+/// git prints in hunk headers, then scores functions with the same churn,
+/// bug-fix and revert signals used for files.
+///
+/// Only hunks in `files` are counted. A hunk is credited to the function
+/// line above it, so a brand-new function added right after another one may
+/// be credited to its neighbour — an accepted limitation of hunk context.
+pub fn analyze_functions(
+    repo: &Path,
+    since: &str,
+    path_filter: Option<&str>,
+    files: &[String],
+    weights: &Weights,
+) -> Result<Vec<FunctionHotspot>, String> {
+    let attributes = write_attributes_file()?;
+    let git_config = vec![
+        format!("core.attributesFile={}", attributes.display()),
+        format!("diff.scanline-js.xfuncname={JS_XFUNCNAME}"),
+    ];
+
+    let file_set: HashSet<&str> = files.iter().map(String::as_str).collect();
+    let now = chrono::Utc::now().timestamp();
+    let mut acc: HashMap<(String, String), Acc> = HashMap::new();
+
+    let result = for_each_hunk(repo, since, path_filter, &git_config, |hunk: &Hunk| {
+        if !file_set.contains(hunk.file) {
+            return;
+        }
+        let Some(name) = function_name(hunk.context) else {
+            return;
+        };
+        let entry = acc
+            .entry((hunk.file.to_string(), name.to_string()))
+            .or_default();
+        // Several hunks of one commit touching the same function count once.
+        if entry.last_commit == hunk.commit {
+            return;
+        }
+        entry.last_commit = hunk.commit.to_string();
+        entry.commits += 1;
+        let days_ago = ((now - hunk.timestamp) / 86400).max(0) as f64;
+        entry.weighted += (-DECAY_LAMBDA * days_ago).exp();
+        if is_bug_fix(hunk.subject) {
+            entry.bugs += 1;
+        }
+        if is_revert(hunk.subject) {
+            entry.reverts += 1;
+        }
+    });
+    let _ = std::fs::remove_file(&attributes);
+    result?;
+
+    Ok(score(acc, weights))
+}
+
+/// Writes [`ATTRIBUTES`] to a new file in the temp directory. The file is
+/// created exclusively under a fresh name, so an existing file or symlink at
+/// that path is never followed or truncated; the caller removes it.
+fn write_attributes_file() -> Result<PathBuf, String> {
+    use std::io::Write;
+    let dir = std::env::temp_dir();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    for attempt in 0..100u32 {
+        let path = dir.join(format!(
+            "git-scanline-{}-{:08x}.gitattributes",
+            std::process::id(),
+            nanos.wrapping_add(attempt.wrapping_mul(0x9e37_79b9))
+        ));
+        let mut file = match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Cannot create {}: {e}", path.display())),
+        };
+        if let Err(e) = file.write_all(ATTRIBUTES.as_bytes()) {
+            let _ = std::fs::remove_file(&path);
+            return Err(format!("Cannot write {}: {e}", path.display()));
+        }
+        return Ok(path);
+    }
+    Err(format!(
+        "Cannot create a git attributes file in {}",
+        dir.display()
+    ))
+}
+
+fn score(acc: HashMap<(String, String), Acc>, weights: &Weights) -> Vec<FunctionHotspot> {
+    let max_weighted = acc.values().map(|a| a.weighted).fold(0.0001_f64, f64::max);
+    let max_bugs = acc
+        .values()
+        .map(|a| a.bugs as f64)
+        .fold(0.0001_f64, f64::max);
+    let max_reverts = acc
+        .values()
+        .map(|a| a.reverts as f64)
+        .fold(0.0001_f64, f64::max);
+    // Only the three history signals apply at function level; rescale their
+    // weights so a function with every signal at max still scores 100.
+    let wsum = (weights.churn + weights.bugs + weights.reverts).max(f64::EPSILON);
+
+    let mut out: Vec<FunctionHotspot> = acc
+        .into_iter()
+        .map(|((file, function), a)| {
+            let churn_score = a.weighted / max_weighted * 100.0;
+            let bug_fix_score = a.bugs as f64 / max_bugs * 100.0;
+            let revert_score = a.reverts as f64 / max_reverts * 100.0;
+            FunctionHotspot {
+                file,
+                function,
+                hotspot_score: (churn_score * weights.churn
+                    + bug_fix_score * weights.bugs
+                    + revert_score * weights.reverts)
+                    / wsum,
+                churn_score,
+                bug_fix_score,
+                revert_score,
+                commit_count: a.commits,
+                bug_commits: a.bugs,
+                revert_count: a.reverts,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        b.hotspot_score
+            .partial_cmp(&a.hotspot_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.function.cmp(&b.function))
+    });
+    out
+}
+
+/// Extracts a function (or, failing that, type) name from a hunk context line.
+fn function_name(context: &str) -> Option<&str> {
+    if context.is_empty() {
+        return None;
+    }
+    [&*FN_KEYWORD, &*ARROW_BINDING, &*CLASS_DECL]
+        .iter()
+        .find_map(|re| re.captures(context).and_then(|c| c.get(1)))
+        .or_else(|| {
+            CALL_LIKE
+                .captures_iter(context)
+                .filter_map(|c| c.get(1))
+                .find(|m| !NOT_NAMES.contains(&m.as_str()))
+        })
+        .map(|m| m.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_file_is_created_fresh() {
+        let a = write_attributes_file().unwrap();
+        let b = write_attributes_file().unwrap();
+        assert_ne!(a, b);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), ATTRIBUTES);
+        let _ = std::fs::remove_file(&a);
+        let _ = std::fs::remove_file(&b);
+    }
+
+    #[test]
+    fn test_function_name_per_language() {
+        assert_eq!(
+            function_name("pub(crate) fn parse_log(cwd: &Path) -> Result<(), String> {"),
+            Some("parse_log")
+        );
+        assert_eq!(
+            function_name("func (r *Repo) Open(path string) error {"),
+            Some("Open")
+        );
+        assert_eq!(function_name("def handle(self, request):"), Some("handle"));
+        assert_eq!(
+            function_name("public static void main(String[] args) {"),
+            Some("main")
+        );
+        assert_eq!(
+            function_name("export const fetchUser = async (id: string) => {"),
+            Some("fetchUser")
+        );
+        assert_eq!(
+            function_name("export default function App() {"),
+            Some("App")
+        );
+    }
+
+    #[test]
+    fn test_function_name_falls_back_to_type_or_none() {
+        assert_eq!(
+            function_name("class PaymentService:"),
+            Some("PaymentService")
+        );
+        assert_eq!(function_name(""), None);
+        assert_eq!(function_name("impl Default for Weights {"), None);
+    }
+
+    #[test]
+    fn test_score_ranks_buggy_function_first() {
+        let mut acc = HashMap::new();
+        acc.insert(
+            ("a.rs".to_string(), "stable".to_string()),
+            Acc {
+                commits: 3,
+                weighted: 3.0,
+                ..Default::default()
+            },
+        );
+        acc.insert(
+            ("a.rs".to_string(), "buggy".to_string()),
+            Acc {
+                commits: 3,
+                weighted: 3.0,
+                bugs: 3,
+                reverts: 1,
+                ..Default::default()
+            },
+        );
+        let out = score(acc, &Weights::default());
+        assert_eq!(out[0].function, "buggy");
+        assert!(
+            (out[0].hotspot_score - 100.0).abs() < 1e-6,
+            "All three signals at max should score 100"
+        );
+        assert!(out[1].hotspot_score < out[0].hotspot_score);
+    }
+}
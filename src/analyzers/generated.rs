@@ -0,0 +1,280 @@
+use crate::types::GeneratedFile;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Only the comments at the top of a file, within this many lines, are
+/// inspected for generator and license banners.
+const HEADER_LINES: usize = 40;
+
+/// A line this long is never hand-written.
+const MINIFIED_MAX_LINE: usize = 1000;
+
+/// Average line length above which a non-trivial file counts as minified.
+const MINIFIED_AVG_LINE: usize = 300;
+
+/// Smaller files are too short for the line-length heuristic to be reliable.
+const MINIFIED_MIN_BYTES: usize = 1024;
+
+/// Banners emitted by code generators, matched against the leading comments:
+/// `@generated`, Go's `Code generated ... DO NOT EDIT.`, a comment that opens
+/// with "auto-generated", or a named generator (protoc, Thrift, OpenAPI, ...).
+static GENERATED_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?im)@generated\b|^\s*code generated\b.*\bdo not edit\b|^\s*(?:(?:this|the) (?:file|code|class) (?:is|was|has been) )?(?:auto[- ]?generated|automatically generated)\b|\bgenerated by (?:the )?(?:protoc|protocol buffer compiler|thrift|swagger|openapi|sqlc|mockgen|bindgen)\b",
+    )
+    .expect("generated marker regex")
+});
+
+/// A well-known library and its version opening the first comment
+/// (`@license Lodash 4.17.21`).
+static VENDORED_BANNER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:@license\s+)?(?:jquery|lodash|underscore|bootstrap|moment|backbone|angular|react|vue|d3)(?:\.js)?\s+v?\d+\.\d+\.\d+",
+    )
+    .expect("vendored banner regex")
+});
+
+/// Any name and version opening a `/*!` comment, the banner minifiers
+/// preserve (`/*! jQuery v3.7.1 | ...`).
+static PRESERVED_BANNER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*[A-Za-z][\w.\-]*\s+v?\d+\.\d+\.\d+\b").expect("preserved banner regex")
+});
+
+/// One comment at the top of a file, markers stripped. Consecutive line
+/// comments form one comment.
+#[derive(Debug, Default)]
+struct Comment {
+    text: String,
+    /// Opened with `/*!`, which minifiers keep.
+    preserved: bool,
+}
+
+impl Comment {
+    fn push(&mut self, line: &str) {
+        self.text.push_str(line.trim());
+        self.text.push('\n');
+    }
+}
+
+/// Returns `file` with the reason it looks generated, minified or vendored,
+/// judged from its `content` at HEAD, or `None` for ordinary source.
+pub fn detect_generated(file: &str, content: &str) -> Option<GeneratedFile> {
+    classify(content).map(|reason| GeneratedFile {
+        file: file.to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+/// Returns `generated`, `minified` or `vendored` when `content` matches a heuristic.
+fn classify(content: &str) -> Option<&'static str> {
+    let comments = leading_comments(content);
+    if comments.iter().any(|c| GENERATED_MARKER.is_match(&c.text)) {
+        return Some("generated");
+    }
+    if let Some(first) = comments.first() {
+        if VENDORED_BANNER.is_match(&first.text)
+            || (first.preserved && PRESERVED_BANNER.is_match(&first.text))
+        {
+            return Some("vendored");
+        }
+    }
+    if is_minified(content) {
+        return Some("minified");
+    }
+    None
+}
+
+/// The comments before the first line of code, within [`HEADER_LINES`].
+/// Understands `//`, `#`, `--`, `/* */` and `<!-- -->` comments; `#include`
+/// and other `#word` directives count as code.
+fn leading_comments(content: &str) -> Vec<Comment> {
+    let mut comments = Vec::new();
+    let mut run: Option<Comment> = None;
+    let mut block: Option<(&str, Comment)> = None;
+    for line in content.lines().take(HEADER_LINES) {
+        let t = line.trim();
+        if let Some((close, mut comment)) = block.take() {
+            match t.split_once(close) {
+                Some((body, _)) => {
+                    comment.push(body.trim_start_matches('*'));
+                    comments.push(comment);
+                }
+                None => {
+                    comment.push(t.trim_start_matches('*'));
+                    block = Some((close, comment));
+                }
+            }
+            continue;
+        }
+        if let Some(text) = line_comment(t) {
+            run.get_or_insert_with(Comment::default).push(text);
+            continue;
+        }
+        comments.extend(run.take());
+        if t.is_empty() {
+            continue;
+        }
+        let (rest, close, preserved) = if let Some(rest) = t.strip_prefix("/*") {
+            (rest, "*/", rest.starts_with('!'))
+        } else if let Some(rest) = t.strip_prefix("<!--") {
+            (rest, "-->", false)
+        } else {
+            break;
+        };
+        let mut comment = Comment {
+            preserved,
+            ..Comment::default()
+        };
+        let rest = rest.trim_start_matches(['*', '!']);
+        match rest.split_once(close) {
+            Some((body, _)) => {
+                comment.push(body);
+                comments.push(comment);
+            }
+            None => {
+                comment.push(rest);
+                block = Some((close, comment));
+            }
+        }
+    }
+    comments.extend(run);
+    comments.extend(block.map(|(_, c)| c));
+    comments
+}
+
+/// Text of a `//`, `#` or `--` line comment.
+fn line_comment(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix("//") {
+        return Some(rest.trim_start_matches(['/', '!']));
+    }
+    for prefix in ["#", "--"] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            if rest.is_empty() || rest.starts_with([' ', '\t', '!', '#']) {
+                return Some(rest.trim_start_matches(['#', '!']));
+            }
+        }
+    }
+    None
+}
+
+fn is_minified(content: &str) -> bool {
+    if content.len() < MINIFIED_MIN_BYTES {
+        return false;
+    }
+    let (count, longest) = content
+        .lines()
+        .fold((0usize, 0usize), |(n, max), l| (n + 1, max.max(l.len())));
+    longest > MINIFIED_MAX_LINE || content.len() / count.max(1) > MINIFIED_AVG_LINE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_markers_detected() {
+        assert_eq!(
+            classify("// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n"),
+            Some("generated")
+        );
+        assert_eq!(
+            classify("# @generated by sqlc\nclass Foo: pass\n"),
+            Some("generated")
+        );
+        assert_eq!(
+            classify("/* This file is auto-generated */\n"),
+            Some("generated")
+        );
+        assert_eq!(
+            classify("#!/usr/bin/env python\n# -*- coding: utf-8 -*-\n# Generated by the protocol buffer compiler.  DO NOT EDIT!\nimport sys\n"),
+            Some("generated")
+        );
+    }
+
+    #[test]
+    fn test_marker_below_header_is_ignored() {
+        let mut content = "fn main() {}\n".repeat(HEADER_LINES + 5);
+        content.push_str("// do not edit\n");
+        assert_eq!(
+            classify(&content),
+            None,
+            "Markers deep in the file are usually comments, not banners"
+        );
+    }
+
+    #[test]
+    fn test_vendored_banner_detected() {
+        assert_eq!(
+            classify("/*! jQuery v3.7.1 | (c) OpenJS Foundation | jquery.org/license */\n"),
+            Some("vendored")
+        );
+        assert_eq!(
+            classify("/**\n * @license Lodash 4.17.21\n */\n"),
+            Some("vendored")
+        );
+    }
+
+    #[test]
+    fn test_doxygen_comment_is_not_vendored() {
+        assert_eq!(
+            classify("/*! \\brief Parses the input. */\nint parse(const char *s);\n"),
+            None
+        );
+        assert_eq!(
+            classify("/*!\n    \\class QWidget\n    \\since 5.15.2\n*/\nclass QWidget;\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_own_license_header_with_version_is_not_vendored() {
+        assert_eq!(
+            classify("/**\n * @license MIT\n * Acme Widgets v2.1.0\n */\nexport const a = 1;\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_library_mentioned_in_comment_is_not_vendored() {
+        assert_eq!(
+            classify("// works around React 18.2 bug\nexport function fix() {}\n"),
+            None
+        );
+        assert_eq!(
+            classify("// Wrapper for our widgets\n/*! jQuery v3.7.1 */\n"),
+            None,
+            "The banner must be the first comment"
+        );
+    }
+
+    #[test]
+    fn test_generated_words_in_code_or_prose_are_not_markers() {
+        assert_eq!(
+            classify("fn main() {}\n// do not edit this table by hand\n"),
+            None
+        );
+        assert_eq!(classify("let ids = next(); // auto-generated ids\n"), None);
+        assert_eq!(
+            classify("// Note: do not edit the schema without a migration.\nfn a() {}\n"),
+            None
+        );
+        assert_eq!(
+            classify("#include <stdio.h>\n/* auto-generated below */\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_minified_detected_by_line_length() {
+        let content = format!("var a=\"{}\";", "x".repeat(MINIFIED_MAX_LINE + 100));
+        assert_eq!(classify(&content), Some("minified"));
+    }
+
+    #[test]
+    fn test_ordinary_source_not_flagged() {
+        let content =
+            "use std::io;\n\n/// Reads input.\nfn read() -> String {\n    String::new()\n}\n"
+                .repeat(50);
+        assert_eq!(classify(&content), None);
+    }
+}
@@ -0,0 +1,95 @@
+use super::bug_correlation::is_bug_fix;
+use super::revert_tracker::is_revert;
+use crate::types::{AuthorShare, Commit, FileCommit, FileHistory};
+use std::collections::{HashMap, HashSet};
+
+/// Newest commits kept per file; author shares still count every commit.
+pub const MAX_COMMITS: usize = 50;
+
+/// Commit list and author distribution of each file in `files`, for the
+/// drill-down of reported results.
+pub fn file_histories(commits: &[Commit], files: &[String]) -> HashMap<String, FileHistory> {
+    let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let mut touching: HashMap<&str, Vec<&Commit>> = HashMap::new();
+    for commit in commits {
+        for file in &commit.files {
+            if file_set.contains(file.as_str()) {
+                touching.entry(file.as_str()).or_default().push(commit);
+            }
+        }
+    }
+
+    touching
+        .into_iter()
+        .map(|(file, mut list)| {
+            list.sort_by_key(|c| std::cmp::Reverse(c.timestamp));
+
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for c in &list {
+                *counts.entry(c.author.as_str()).or_insert(0) += 1;
+            }
+            let mut authors: Vec<AuthorShare> = counts
+                .into_iter()
+                .map(|(author, n)| AuthorShare {
+                    author: author.to_string(),
+                    commits: n,
+                    percent: n as f64 / list.len() as f64 * 100.0,
+                })
+                .collect();
+            authors.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.author.cmp(&b.author)));
+
+            let commits = list
+                .iter()
+                .take(MAX_COMMITS)
+                .map(|c| FileCommit {
+                    hash: c.hash.clone(),
+                    author: c.author.clone(),
+                    date: chrono::DateTime::from_timestamp(c.timestamp, 0)
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default(),
+                    subject: c.subject.clone(),
+                    bug_fix: is_bug_fix(&c.subject),
+                    revert: is_revert(&c.subject),
+                })
+                .collect();
+            (file.to_string(), FileHistory { commits, authors })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, timestamp: i64, subject: &str, files: &[&str]) -> Commit {
+        Commit {
+            hash: format!("h{timestamp}"),
+            author: author.to_string(),
+            timestamp,
+            subject: subject.to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_history_newest_first_with_author_shares() {
+        let commits = vec![
+            commit("ann", 1_700_000_000, "add parser", &["a.rs", "b.rs"]),
+            commit("bo", 1_700_100_000, "fix crash in parser", &["a.rs"]),
+            commit("ann", 1_700_200_000, "Revert \"fix crash\"", &["a.rs"]),
+            commit("ann", 1_700_300_000, "docs", &["README.md"]),
+        ];
+        let files = vec!["a.rs".to_string()];
+        let histories = file_histories(&commits, &files);
+        assert_eq!(histories.len(), 1, "Only requested files");
+
+        let h = &histories["a.rs"];
+        let hashes: Vec<&str> = h.commits.iter().map(|c| c.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["h1700200000", "h1700100000", "h1700000000"]);
+        assert!(h.commits[0].revert && h.commits[1].bug_fix);
+        assert_eq!(h.commits[2].date, "2023-11-14");
+        assert_eq!(h.authors[0].author, "ann");
+        assert_eq!(h.authors[0].commits, 2);
+        assert!((h.authors[1].percent - 100.0 / 3.0).abs() < 1e-9);
+    }
+}
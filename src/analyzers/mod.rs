@@ -2,7 +2,18 @@ pub mod blame;
 pub mod bug_correlation;
 pub mod burst_detector;
 pub mod churn;
+pub mod code_age;
 pub mod commit_quality;
+pub mod complexity;
+pub mod contributors;
 pub mod coupling;
+pub mod coverage;
+pub mod entropy;
+pub mod experience;
+pub mod functions;
+pub mod generated;
+pub mod history;
 pub mod revert_tracker;
+pub mod secrets;
 pub mod security;
+pub mod test_coevolution;
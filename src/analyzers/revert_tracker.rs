@@ -4,6 +4,11 @@ use std::collections::{HashMap, HashSet};
 static REVERT_PATTERN: once_cell::sync::Lazy<regex::Regex> =
     once_cell::sync::Lazy::new(|| regex::Regex::new(r"(?i)^revert\b").unwrap());
 
+/// Returns true when a commit subject is a revert (`Revert "..."`).
+pub fn is_revert(subject: &str) -> bool {
+    REVERT_PATTERN.is_match(subject.trim())
+}
+
 /// Detects files appearing in revert commits — a strong signal of introduced bugs.
 pub fn analyze_reverts(commits: &[Commit], files: &[String]) -> HashMap<String, RevertData> {
     let file_set: HashSet<&str> = files.iter().map(|s| s.as_str()).collect();
@@ -11,7 +16,7 @@ pub fn analyze_reverts(commits: &[Commit], files: &[String]) -> HashMap<String,
     let mut file_reverts: HashMap<String, usize> = HashMap::new();
 
     for commit in commits {
-        if !REVERT_PATTERN.is_match(commit.subject.trim()) {
+        if !is_revert(&commit.subject) {
             continue;
         }
         for file in &commit.files {
@@ -109,7 +114,7 @@ mod tests {
         ];
         let files = vec!["a.rs".to_string(), "b.rs".to_string()];
         let result = analyze_reverts(&commits, &files);
-        for (_, data) in &result {
+        for data in result.values() {
             assert!(
                 data.revert_score >= 0.0 && data.revert_score <= 100.0,
                 "revert_score {} out of range",
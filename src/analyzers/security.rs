@@ -1,6 +1,11 @@
-use crate::types::{Commit, SecurityRisk};
+use crate::config::SecurityOverrides;
+use crate::filters::compile_glob;
+use crate::git::log_parser::file_lifecycles;
+use crate::git::tree::{list_refs, paths_in_revs};
+use crate::types::{Commit, SecretFinding, SecurityRisk};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::path::Path;
 
 static ENV_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)(?:^|/)\.(env)(\.|$)").expect("env regex"));
@@ -11,42 +16,120 @@ static CRED_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)(?:^|/)(?:credential|secret|password|passwd|private[_-]?key|api[_-]?key|auth[_-]?token)[^/]*$").expect("cred regex")
 });
 
-fn get_risk_type(file: &str) -> Option<&'static str> {
+/// A compiled custom rule from the `security.rules` config section.
+struct CustomRule {
+    pattern: Regex,
+    risk_type: String,
+    severity: String,
+}
+
+/// Compiled allowlist from the `security.allowlist` config section.
+struct Allowlist {
+    paths: Vec<Regex>,
+    commits: Vec<String>,
+}
+
+impl Allowlist {
+    fn new(overrides: &SecurityOverrides) -> Self {
+        Allowlist {
+            paths: overrides
+                .allow_paths
+                .iter()
+                .filter_map(|p| compile_glob(p).ok())
+                .collect(),
+            commits: overrides
+                .allow_commits
+                .iter()
+                .map(|c| c.to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    fn allows_path(&self, file: &str) -> bool {
+        self.paths.iter().any(|re| re.is_match(file))
+    }
+
+    fn allows_commit(&self, hash: &str) -> bool {
+        let hash = hash.to_ascii_lowercase();
+        self.commits.iter().any(|c| hash.starts_with(c.as_str()))
+    }
+}
+
+fn get_risk_type<'a>(file: &str, custom: &'a [CustomRule]) -> Option<(&'a str, &'a str)> {
+    if let Some(rule) = custom.iter().find(|r| r.pattern.is_match(file)) {
+        return Some((&rule.risk_type, &rule.severity));
+    }
     if ENV_PATTERN.is_match(file) {
-        return Some("env-file");
+        return Some(("env-file", "high"));
     }
     if KEY_PATTERN.is_match(file) {
-        return Some("key-or-cert");
+        return Some(("key-or-cert", "critical"));
     }
     if CRED_PATTERN.is_match(file) {
-        return Some("credential-file");
+        return Some(("credential-file", "high"));
     }
     None
 }
 
+/// Sort rank for a severity label; unknown labels sort last.
+fn severity_rank(severity: &str) -> usize {
+    crate::config::SEVERITIES
+        .iter()
+        .position(|s| *s == severity)
+        .unwrap_or(usize::MAX)
+}
+
 /// Scans raw (unfiltered) commits for security-sensitive files ever committed
 /// to git history. Even deleted files are flagged — they remain accessible.
-pub fn analyze_security(commits: &[Commit]) -> Vec<SecurityRisk> {
-    use std::collections::HashMap;
+///
+/// Custom rules from `overrides` are checked before the built-in patterns.
+/// Findings on allowlisted paths or in allowlisted commits are dropped; the
+/// number of suppressed files is returned alongside the risks.
+pub fn analyze_security(
+    commits: &[Commit],
+    overrides: &SecurityOverrides,
+) -> (Vec<SecurityRisk>, usize) {
+    use std::collections::{HashMap, HashSet};
 
     struct Entry {
-        risk_type: &'static str,
+        risk_type: String,
+        severity: String,
         count: usize,
         first: i64,
         last: i64,
+        authors: HashMap<String, usize>,
     }
 
+    let custom: Vec<CustomRule> = overrides
+        .rules
+        .iter()
+        .filter_map(|r| {
+            Some(CustomRule {
+                pattern: Regex::new(&r.pattern).ok()?,
+                risk_type: r.risk_type.clone(),
+                severity: r.severity.clone().unwrap_or_else(|| "high".to_string()),
+            })
+        })
+        .collect();
+    let allowlist = Allowlist::new(overrides);
+
     let mut risks: HashMap<String, Entry> = HashMap::new();
+    let mut suppressed: HashSet<String> = HashSet::new();
 
     for commit in commits {
         for file in &commit.files {
-            let Some(risk_type) = get_risk_type(file) else {
+            let Some((risk_type, severity)) = get_risk_type(file, &custom) else {
                 continue;
             };
+            if allowlist.allows_path(file) || allowlist.allows_commit(&commit.hash) {
+                suppressed.insert(file.clone());
+                continue;
+            }
             risks
                 .entry(file.clone())
                 .and_modify(|e| {
                     e.count += 1;
+                    *e.authors.entry(commit.author.clone()).or_insert(0) += 1;
                     if commit.timestamp < e.first {
                         e.first = commit.timestamp;
                     }
@@ -55,27 +138,105 @@ pub fn analyze_security(commits: &[Commit]) -> Vec<SecurityRisk> {
                     }
                 })
                 .or_insert(Entry {
-                    risk_type,
+                    risk_type: risk_type.to_string(),
+                    severity: severity.to_string(),
                     count: 1,
                     first: commit.timestamp,
                     last: commit.timestamp,
+                    authors: HashMap::from([(commit.author.clone(), 1)]),
                 });
         }
     }
 
     let mut out: Vec<SecurityRisk> = risks
         .into_iter()
-        .map(|(file, e)| SecurityRisk {
-            file,
-            risk_type: e.risk_type.to_string(),
-            commit_count: e.count,
-            first_seen: fmt_date(e.first),
-            last_seen: fmt_date(e.last),
+        .map(|(file, e)| {
+            let mut authors: Vec<(String, usize)> = e.authors.into_iter().collect();
+            authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            SecurityRisk {
+                file,
+                risk_type: e.risk_type,
+                severity: e.severity,
+                commit_count: e.count,
+                first_seen: fmt_date(e.first),
+                last_seen: fmt_date(e.last),
+                present_at_head: false,
+                refs: Vec::new(),
+                added_in: Vec::new(),
+                removed_in: Vec::new(),
+                authors: authors.into_iter().map(|(a, _)| a).collect(),
+            }
         })
         .collect();
 
-    out.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
-    out
+    out.sort_by(|a, b| {
+        severity_rank(&a.severity)
+            .cmp(&severity_rank(&b.severity))
+            .then_with(|| b.commit_count.cmp(&a.commit_count))
+    });
+
+    // A file only counts as suppressed if none of its commits were reported.
+    let suppressed_count = suppressed
+        .iter()
+        .filter(|f| !out.iter().any(|r| &r.file == *f))
+        .count();
+    (out, suppressed_count)
+}
+
+/// Fills in where each risk still lives: whether the file is in the HEAD tree,
+/// which branches and tags contain it at their tip, and which commits (on any
+/// ref) added and removed it. A risk that is absent from every ref but still
+/// has `added_in` commits is only reachable through history — purging it
+/// requires a history rewrite rather than a simple delete.
+///
+/// Whatever could be looked up is filled in even when a git call fails; the
+/// error is returned so the caller can warn without dropping the risks.
+pub fn locate_risks(repo: &Path, risks: &mut [SecurityRisk]) -> Result<(), String> {
+    if risks.is_empty() {
+        return Ok(());
+    }
+    let paths: Vec<String> = risks.iter().map(|r| r.file.clone()).collect();
+
+    let located = list_refs(repo).and_then(|refs| {
+        let revs: Vec<String> = std::iter::once("HEAD".to_string()).chain(refs).collect();
+        let found = paths_in_revs(repo, &revs, &paths)?;
+        Ok((revs, found))
+    });
+    let lifecycles = file_lifecycles(repo, &paths);
+
+    if let Ok((revs, found)) = &located {
+        for risk in risks.iter_mut() {
+            let has = |rev: &str| found.get(rev).is_some_and(|f| f.contains(&risk.file));
+            risk.present_at_head = has("HEAD");
+            // Ref order from for-each-ref: branches, then tags, by name.
+            risk.refs = revs[1..].iter().filter(|r| has(r)).cloned().collect();
+        }
+    }
+    if let Ok(lifecycles) = &lifecycles {
+        for risk in risks.iter_mut() {
+            if let Some(lc) = lifecycles.get(&risk.file) {
+                risk.added_in = lc.added_in.clone();
+                risk.removed_in = lc.removed_in.clone();
+            }
+        }
+    }
+    located.and(lifecycles).map(|_| ())
+}
+
+/// Drops secret findings on allowlisted paths or in allowlisted commits.
+/// Returns the kept findings and the number suppressed.
+pub fn suppress_secret_findings(
+    findings: Vec<SecretFinding>,
+    overrides: &SecurityOverrides,
+) -> (Vec<SecretFinding>, usize) {
+    let allowlist = Allowlist::new(overrides);
+    let total = findings.len();
+    let kept: Vec<SecretFinding> = findings
+        .into_iter()
+        .filter(|f| !allowlist.allows_path(&f.file) && !allowlist.allows_commit(&f.commit))
+        .collect();
+    let suppressed = total - kept.len();
+    (kept, suppressed)
 }
 
 fn fmt_date(ts: i64) -> String {
@@ -85,3 +246,94 @@ fn fmt_date(ts: i64) -> String {
         .map(|dt| dt.format("%Y-%m-%d").to_string())
         .unwrap_or_else(|| "unknown".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SecurityRuleConfig;
+
+    fn make_commit(hash: &str, files: &[&str]) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            author: "dev@test.com".to_string(),
+            timestamp: 1700000000,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_adds_risk_type_and_severity() {
+        let overrides = SecurityOverrides {
+            rules: vec![SecurityRuleConfig {
+                pattern: r"\.tfstate$".to_string(),
+                risk_type: "terraform-state".to_string(),
+                severity: Some("critical".to_string()),
+            }],
+            ..Default::default()
+        };
+        let commits = vec![make_commit("abc1", &["infra/prod.tfstate"])];
+        let (risks, _) = analyze_security(&commits, &overrides);
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].risk_type, "terraform-state");
+        assert_eq!(risks[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_allowlisted_path_is_suppressed_and_counted() {
+        let overrides = SecurityOverrides {
+            allow_paths: vec![".env.example".to_string(), "test/fixtures/**".to_string()],
+            ..Default::default()
+        };
+        let commits = vec![make_commit(
+            "abc1",
+            &[".env", ".env.example", "test/fixtures/dev.pem"],
+        )];
+        let (risks, suppressed) = analyze_security(&commits, &overrides);
+        let flagged: Vec<&str> = risks.iter().map(|r| r.file.as_str()).collect();
+        assert_eq!(flagged, vec![".env"]);
+        assert_eq!(suppressed, 2, "Both allowlisted files should be counted");
+    }
+
+    #[test]
+    fn test_allowlisted_commit_prefix_is_suppressed() {
+        let overrides = SecurityOverrides {
+            allow_commits: vec!["DEADBEEF".to_string()],
+            ..Default::default()
+        };
+        let commits = vec![
+            make_commit("deadbeef0123", &["secrets/api_key.txt"]),
+            make_commit("cafe0123", &[".env"]),
+        ];
+        let (risks, suppressed) = analyze_security(&commits, &overrides);
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].file, ".env");
+        assert_eq!(suppressed, 1);
+    }
+
+    #[test]
+    fn test_authors_listed_most_active_first() {
+        let mut a = make_commit("a1", &[".env"]);
+        a.author = "alice@test.com".to_string();
+        let mut b = make_commit("b1", &[".env"]);
+        b.author = "bob@test.com".to_string();
+        let mut b2 = make_commit("b2", &[".env"]);
+        b2.author = "bob@test.com".to_string();
+        let (risks, _) = analyze_security(&[a, b, b2], &SecurityOverrides::default());
+        assert_eq!(risks[0].authors, vec!["bob@test.com", "alice@test.com"]);
+    }
+
+    #[test]
+    fn test_risks_sorted_by_severity() {
+        let commits = vec![
+            make_commit("a1", &[".env"]),
+            make_commit("a2", &[".env"]),
+            make_commit("a3", &["certs/server.key"]),
+        ];
+        let (risks, _) = analyze_security(&commits, &SecurityOverrides::default());
+        assert_eq!(
+            risks[0].file, "certs/server.key",
+            "Critical risks should sort before high ones regardless of commit count"
+        );
+    }
+}
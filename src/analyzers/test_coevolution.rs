@@ -0,0 +1,289 @@
+use crate::config::TestRuleConfig;
+use crate::types::{Commit, TestCoevolution};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Directory names whose files are tests even without a test affix.
+const TEST_DIRS: &[&str] = &["test", "tests", "__tests__", "spec", "specs"];
+
+/// Name suffixes marking a test file (`foo_test.go`, `foo.spec.ts`, `FooTest.java`).
+const TEST_SUFFIXES: &[&str] = &[
+    "_test", "_tests", "_spec", ".test", ".spec", "Test", "Tests", "Spec",
+];
+
+/// Stems too generic to pair on directory placement alone (`tests/common/mod.rs`).
+const GENERIC_STEMS: &[&str] = &["mod", "index", "__init__", "conftest", "main", "lib"];
+
+/// Pairs each source file in `files` with its test files and measures how
+/// often the two change together.
+///
+/// Test files are looked up in `head_paths` (every path at HEAD), so tests
+/// that did not change in the analyzed window still count. A file is paired
+/// with tests that share its stem and language — preferring the closest
+/// directory — and with any path produced by a matching config rule. Files
+/// that are tests themselves, or not code, are absent from the result.
+/// `inline_tests` holds the files whose content at HEAD passed
+/// [`has_inline_tests`].
+pub fn analyze_test_coevolution(
+    commits: &[Commit],
+    files: &[String],
+    head_paths: &[String],
+    inline_tests: &HashSet<String>,
+    rules: &[TestRuleConfig],
+) -> HashMap<String, TestCoevolution> {
+    let rules: Vec<(Regex, &str)> = rules
+        .iter()
+        .filter_map(|r| Regex::new(&r.source).ok().map(|re| (re, r.test.as_str())))
+        .collect();
+    let head_set: HashSet<&str> = head_paths.iter().map(String::as_str).collect();
+
+    // (language, subject stem) → test files at HEAD
+    let mut tests_by_subject: HashMap<(&str, String), Vec<&str>> = HashMap::new();
+    for path in head_paths {
+        if let (Some(lang), Some(subject)) = (language(path), test_subject(path)) {
+            tests_by_subject
+                .entry((lang, subject))
+                .or_default()
+                .push(path);
+        }
+    }
+
+    let mut pairs: HashMap<&str, Vec<String>> = HashMap::new();
+    for file in files {
+        if test_subject(file).is_some() {
+            continue;
+        }
+        let mut tests: Vec<String> = Vec::new();
+        let mut ruled = false;
+        for (re, template) in &rules {
+            if let Some(caps) = re.captures(file) {
+                ruled = true;
+                let mut target = String::new();
+                caps.expand(template, &mut target);
+                if head_set.contains(target.as_str()) {
+                    tests.push(target);
+                }
+            }
+        }
+        let Some(lang) = language(file) else {
+            if ruled {
+                pairs.insert(file, tests);
+            }
+            continue;
+        };
+        let key = (lang, stem(file).to_string());
+        if let Some(candidates) = tests_by_subject.get(&key) {
+            let closest = candidates
+                .iter()
+                .map(|t| shared_dirs(file, t))
+                .max()
+                .unwrap_or(0);
+            tests.extend(
+                candidates
+                    .iter()
+                    .filter(|t| shared_dirs(file, t) == closest)
+                    .map(|t| t.to_string()),
+            );
+        }
+        tests.sort();
+        tests.dedup();
+        pairs.insert(file, tests);
+    }
+
+    let mut touched: HashMap<&str, usize> = HashMap::new();
+    let mut together: HashMap<&str, usize> = HashMap::new();
+    for commit in commits {
+        let in_commit: HashSet<&str> = commit.files.iter().map(String::as_str).collect();
+        for f in &commit.files {
+            let Some((file, tests)) = pairs.get_key_value(f.as_str()) else {
+                continue;
+            };
+            *touched.entry(file).or_insert(0) += 1;
+            if tests.iter().any(|t| in_commit.contains(t.as_str())) {
+                *together.entry(file).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|(file, test_files)| {
+            let inline_tests = inline_tests.contains(file);
+            let commits = touched.get(file).copied().unwrap_or(0);
+            let co_changed_commits = together.get(file).copied().unwrap_or(0);
+            let co_change_ratio = (!test_files.is_empty() && commits > 0)
+                .then(|| co_changed_commits as f64 / commits as f64);
+            let test_gap_score = match co_change_ratio {
+                Some(ratio) => (1.0 - ratio) * 100.0,
+                // Paired tests that never changed in the window: all commits left them alone.
+                None if !test_files.is_empty() => 100.0,
+                // Inline tests change in the same file, so co-change cannot be told apart.
+                None if inline_tests => 0.0,
+                None => 100.0,
+            };
+            let data = TestCoevolution {
+                test_files,
+                inline_tests,
+                co_changed_commits,
+                co_change_ratio,
+                test_gap_score,
+            };
+            (file.to_string(), data)
+        })
+        .collect()
+}
+
+/// Whether `content` carries its own unit tests (a Rust `#[cfg(test)]` module).
+pub fn has_inline_tests(content: &str) -> bool {
+    content.contains("#[cfg(test)]")
+}
+
+/// Returns the stem of the file a test covers (`foo` for `foo_test.go`,
+/// `tests/foo.rs` or `test_foo.py`), or `None` if `path` is not a test.
+fn test_subject(path: &str) -> Option<String> {
+    language(path)?;
+    let s = stem(path);
+    for suffix in TEST_SUFFIXES {
+        if let Some(subject) = s.strip_suffix(suffix).filter(|r| !r.is_empty()) {
+            return Some(subject.to_string());
+        }
+    }
+    if let Some(subject) = s.strip_prefix("test_").filter(|r| !r.is_empty()) {
+        return Some(subject.to_string());
+    }
+    // TestFoo.java, but not Testimonial.java
+    if let Some(subject) = s
+        .strip_prefix("Test")
+        .filter(|r| r.starts_with(|c: char| c.is_ascii_uppercase()))
+    {
+        return Some(subject.to_string());
+    }
+    let in_test_dir = path
+        .split('/')
+        .rev()
+        .skip(1)
+        .any(|seg| TEST_DIRS.contains(&seg));
+    (in_test_dir && !GENERIC_STEMS.contains(&s)).then(|| s.to_string())
+}
+
+/// File name without directory or final extension.
+fn stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map_or(name, |(s, _)| s)
+}
+
+/// Language family by extension; tests only pair within a family.
+fn language(path: &str) -> Option<&'static str> {
+    let ext = path.rsplit_once('.')?.1;
+    Some(match ext {
+        "rs" => "rust",
+        "go" => "go",
+        "py" => "python",
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => "js",
+        "java" | "kt" | "scala" => "jvm",
+        "rb" => "ruby",
+        "cs" => "csharp",
+        "php" => "php",
+        "swift" => "swift",
+        "c" | "cc" | "cpp" | "cxx" | "h" | "hpp" => "c",
+        _ => return None,
+    })
+}
+
+/// Number of leading directories the two paths have in common.
+fn shared_dirs(a: &str, b: &str) -> usize {
+    let dirs_a = a.rsplit_once('/').map_or("", |(d, _)| d);
+    let dirs_b = b.rsplit_once('/').map_or("", |(d, _)| d);
+    dirs_a
+        .split('/')
+        .zip(dirs_b.split('/'))
+        .take_while(|(x, y)| !x.is_empty() && x == y)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(files: &[&str]) -> Commit {
+        Commit {
+            hash: "abc".to_string(),
+            author: "dev".to_string(),
+            timestamp: 0,
+            subject: "change".to_string(),
+            files: files.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_subject_by_naming_convention() {
+        assert_eq!(test_subject("pkg/store_test.go").as_deref(), Some("store"));
+        assert_eq!(test_subject("tests/test_api.py").as_deref(), Some("api"));
+        assert_eq!(test_subject("src/app.spec.ts").as_deref(), Some("app"));
+        assert_eq!(
+            test_subject("src/test/java/UserServiceTest.java").as_deref(),
+            Some("UserService")
+        );
+        assert_eq!(test_subject("tests/parser.rs").as_deref(), Some("parser"));
+        assert_eq!(test_subject("src/Testimonial.java"), None);
+        assert_eq!(test_subject("tests/common/mod.rs"), None);
+        assert_eq!(test_subject("docs/test_plan.md"), None, "Not code");
+    }
+
+    #[test]
+    fn test_pairs_closest_test_and_measures_co_change() {
+        let head = strings(&[
+            "a/util.go",
+            "a/util_test.go",
+            "b/util.go",
+            "b/util_test.go",
+            "src/orphan.py",
+        ]);
+        let files = strings(&["a/util.go", "src/orphan.py", "a/util_test.go"]);
+        let commits = vec![
+            commit(&["a/util.go", "a/util_test.go"]),
+            commit(&["a/util.go"]),
+            commit(&["a/util.go", "b/util_test.go"]),
+            commit(&["src/orphan.py"]),
+        ];
+        let result = analyze_test_coevolution(&commits, &files, &head, &HashSet::new(), &[]);
+
+        let util = &result["a/util.go"];
+        assert_eq!(util.test_files, vec!["a/util_test.go".to_string()]);
+        assert_eq!(util.co_changed_commits, 1, "b/util_test.go is not its test");
+        assert!((util.co_change_ratio.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+
+        let orphan = &result["src/orphan.py"];
+        assert!(orphan.test_files.is_empty());
+        assert_eq!(orphan.test_gap_score, 100.0, "No test file at all");
+        assert!(
+            !result.contains_key("a/util_test.go"),
+            "Test files are not scored"
+        );
+    }
+
+    #[test]
+    fn test_config_rule_and_inline_tests() {
+        let head = strings(&["lib/engine.rs", "qa/engine_checks.rs", "src/inline.rs"]);
+        let files = strings(&["lib/engine.rs", "src/inline.rs"]);
+        let rules = vec![TestRuleConfig {
+            source: r"^lib/(\w+)\.rs$".to_string(),
+            test: "qa/${1}_checks.rs".to_string(),
+        }];
+        assert!(has_inline_tests("fn f() {}\n#[cfg(test)]\nmod tests {}\n"));
+        let inline = HashSet::from(["src/inline.rs".to_string()]);
+        let commits = vec![commit(&["lib/engine.rs", "qa/engine_checks.rs"])];
+        let result = analyze_test_coevolution(&commits, &files, &head, &inline, &rules);
+
+        assert_eq!(result["lib/engine.rs"].co_change_ratio, Some(1.0));
+        assert!(result["src/inline.rs"].inline_tests);
+        assert_eq!(
+            result["src/inline.rs"].test_gap_score, 0.0,
+            "Inline tests count as tested"
+        );
+    }
+}
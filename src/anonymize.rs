@@ -0,0 +1,119 @@
+use crate::types::{AuthorReport, Report};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Salt used when none is configured. Pseudonyms stay stable, but anyone who
+/// knows an author's email can recompute theirs.
+pub const DEFAULT_SALT: &str = "git-scanline";
+
+/// Maps author emails to `author-<8 hex>` pseudonyms derived from a salted
+/// SHA-256 of the lower-cased email, so the same author gets the same name
+/// in every run and every format that uses the same salt.
+pub struct Pseudonymizer {
+    salt: String,
+    cache: HashMap<String, String>,
+}
+
+impl Pseudonymizer {
+    pub fn new(salt: &str) -> Self {
+        Pseudonymizer {
+            salt: salt.to_string(),
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn name(&mut self, email: &str) -> String {
+        // Placeholder for files with no known author, not a person.
+        if email == "unknown" {
+            return email.to_string();
+        }
+        let salt = &self.salt;
+        self.cache
+            .entry(email.to_string())
+            .or_insert_with(|| {
+                let mut hasher = Sha256::new();
+                hasher.update(salt.as_bytes());
+                hasher.update([0]);
+                hasher.update(email.trim().to_lowercase().as_bytes());
+                let digest = hasher.finalize();
+                let hex: String = digest[..4].iter().map(|b| format!("{b:02x}")).collect();
+                format!("author-{hex}")
+            })
+            .clone()
+    }
+
+    fn rename_all(&mut self, emails: &mut [String]) {
+        for e in emails {
+            *e = self.name(e);
+        }
+    }
+}
+
+/// Replaces the author of every commit, for exports of the raw history.
+#[cfg(feature = "sqlite")]
+pub fn anonymize_commits(commits: &mut [crate::types::Commit], salt: &str) {
+    let mut p = Pseudonymizer::new(salt);
+    for c in commits {
+        c.author = p.name(&c.author);
+    }
+}
+
+/// Replaces every author email in the hotspot report. Scores are untouched.
+pub fn anonymize_report(report: &mut Report, salt: &str) {
+    let mut p = Pseudonymizer::new(salt);
+    for r in &mut report.results {
+        r.details.top_author = p.name(&r.details.top_author);
+        if let Some(o) = r.details.ownership.as_mut() {
+            p.rename_all(&mut o.primary_authors);
+        }
+        if let Some(h) = r.details.history.as_mut() {
+            for c in &mut h.commits {
+                c.author = p.name(&c.author);
+            }
+            for a in &mut h.authors {
+                a.author = p.name(&a.author);
+            }
+        }
+    }
+    for k in &mut report.knowledge_risks {
+        k.author = p.name(&k.author);
+    }
+    for s in &mut report.security_risks {
+        p.rename_all(&mut s.authors);
+    }
+    for s in &mut report.secret_findings {
+        s.author = p.name(&s.author);
+    }
+}
+
+/// Replaces author emails in the authors report; team names are kept.
+pub fn anonymize_author_report(report: &mut AuthorReport, salt: &str) {
+    let mut p = Pseudonymizer::new(salt);
+    let by_team = report.group_by == "team";
+    for a in &mut report.authors {
+        if !by_team {
+            a.name = p.name(&a.name);
+        }
+        p.rename_all(&mut a.members);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudonyms_are_stable_and_salted() {
+        let a = Pseudonymizer::new("s1").name("Ann@Example.com");
+        assert_eq!(a, Pseudonymizer::new("s1").name("ann@example.com"));
+        assert!(a.starts_with("author-") && a.len() == "author-".len() + 8);
+        assert!(!a.contains('@'));
+        assert_ne!(
+            a,
+            Pseudonymizer::new("s2").name("ann@example.com"),
+            "A different salt gives different pseudonyms"
+        );
+        assert_ne!(a, Pseudonymizer::new("s1").name("bo@example.com"));
+        assert_eq!(Pseudonymizer::new("s1").name("unknown"), "unknown");
+    }
+}
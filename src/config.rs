@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// All settings that can be placed in a .git-scanline.yml config file.
@@ -12,17 +13,45 @@ pub struct ScanlineConfig {
     pub path: Option<String>,
     pub top: Option<usize>,
     pub bugs_only: Option<bool>,
+    pub all_files: Option<bool>,
+    pub scan_secrets: Option<bool>,
+    pub functions: Option<bool>,
+    pub coverage: Option<String>,
+    pub stable_months: Option<u32>,
+    pub orphan_months: Option<u32>,
+    pub cluster_strength: Option<f64>,
+    pub anonymize: Option<bool>,
+    pub anonymize_salt: Option<String>,
     pub format: Option<String>,
     pub output: Option<String>,
+    pub graph: Option<GraphConfig>,
+    pub treemap_size: Option<String>,
+    pub markdown_max_chars: Option<usize>,
 
     // File-filter overrides
     pub exclude_dirs: Option<Vec<String>>,
     pub include_dirs: Option<Vec<String>>,
     pub exclude_files: Option<Vec<String>>,
     pub exclude_extensions: Option<Vec<String>>,
+    pub exclude_patterns: Option<Vec<String>>,
+    pub respect_gitignore: Option<bool>,
+    pub respect_gitattributes: Option<bool>,
+    pub detect_generated: Option<bool>,
 
     // Scoring weight overrides
     pub weights: Option<ConfigWeights>,
+
+    // Security scan customization
+    pub security: Option<SecurityConfig>,
+
+    // Source ↔ test pairing rules
+    pub tests: Option<TestsConfig>,
+
+    // Architectural components and the dependencies allowed between them
+    pub boundaries: Option<BoundariesConfig>,
+
+    // Team name → member emails, or `@domain` for a whole domain (`authors --teams`)
+    pub teams: Option<BTreeMap<String, Vec<String>>>,
 }
 
 /// Optional per-signal weight overrides. All weights are normalized at runtime.
@@ -36,11 +65,116 @@ pub struct ConfigWeights {
     pub coupling: Option<f64>,
     pub silo: Option<f64>,
     pub commit_quality: Option<f64>,
+    pub complexity: Option<f64>,
+    pub tests: Option<f64>,
+    pub coverage: Option<f64>,
+    pub entropy: Option<f64>,
+    pub experience: Option<f64>,
+}
+
+/// Thresholds for the `dot` and `mermaid` coupling graph formats.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GraphConfig {
+    pub min_strength: Option<f64>,
+    pub max_nodes: Option<usize>,
+    pub directories: Option<bool>,
+}
+
+/// Custom security rules and known-safe exceptions.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityConfig {
+    pub rules: Option<Vec<SecurityRuleConfig>>,
+    pub allowlist: Option<SecurityAllowlist>,
+}
+
+/// A user-defined filename rule, checked before the built-in patterns.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityRuleConfig {
+    /// Regex matched against the repo-relative path.
+    pub pattern: String,
+    pub risk_type: String,
+    /// One of: critical, high, medium, low. Defaults to "high".
+    pub severity: Option<String>,
+}
+
+/// Findings matching any entry here are suppressed and only counted.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityAllowlist {
+    /// Exact paths or globs (`test/fixtures/**`, `.env.example`).
+    pub paths: Option<Vec<String>>,
+    /// Full or abbreviated commit hashes.
+    pub commits: Option<Vec<String>>,
+}
+
+/// Extra source → test pairings, on top of the per-language naming conventions.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestsConfig {
+    pub rules: Option<Vec<TestRuleConfig>>,
+}
+
+/// Maps source paths to a test path by regex substitution.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestRuleConfig {
+    /// Regex matched against the repo-relative source path.
+    pub source: String,
+    /// Test path; `$1`, `${name}` refer to groups captured by `source`.
+    pub test: String,
+}
+
+/// Architectural components; coupling between two of them is reported
+/// unless the dependency is declared in `allow`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoundariesConfig {
+    pub components: Option<Vec<BoundaryComponent>>,
+    /// `"frontend -> api-client"`; `*` matches any component. Coupling has
+    /// no direction, so an entry allows the pair either way round.
+    pub allow: Option<Vec<String>>,
+}
+
+/// A named group of paths. A file belongs to the first component that matches.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoundaryComponent {
+    pub name: String,
+    /// Globs, as in `exclude_patterns` (`frontend/**`, `services/billing/**`).
+    pub paths: Vec<String>,
+}
+
+/// Accepted values for `security.rules[].severity`.
+pub const SEVERITIES: &[&str] = &["critical", "high", "medium", "low"];
+
+/// Resolved security customizations extracted from [`ScanlineConfig`] and
+/// threaded into [`crate::analyzers::security::analyze_security`].
+#[derive(Debug, Default, Clone)]
+pub struct SecurityOverrides {
+    /// Custom rules, checked in order before the built-in patterns.
+    pub rules: Vec<SecurityRuleConfig>,
+    /// Path globs whose findings are suppressed.
+    pub allow_paths: Vec<String>,
+    /// Commit hash prefixes whose findings are suppressed.
+    pub allow_commits: Vec<String>,
+}
+
+/// Resolved `boundaries:` section, threaded into
+/// [`crate::analyzers::coupling::classify_boundaries`].
+#[derive(Debug, Default, Clone)]
+pub struct BoundaryOverrides {
+    /// Components in declaration order.
+    pub components: Vec<BoundaryComponent>,
+    /// Allowed `(from, to)` component pairs; `*` matches any component.
+    pub allow: Vec<(String, String)>,
 }
 
 /// Resolved filter customizations extracted from [`ScanlineConfig`] and
 /// threaded into [`crate::filters::filter_files`].
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct FilterOverrides {
     /// Directory names to add to the built-in exclusion list.
     pub extra_exclude_dirs: Vec<String>,
@@ -50,6 +184,12 @@ pub struct FilterOverrides {
     pub extra_exclude_files: Vec<String>,
     /// File extensions to add to the built-in exclusion list.
     pub extra_exclude_extensions: Vec<String>,
+    /// Gitignore-style globs; `!pattern` re-includes. Last match wins.
+    pub exclude_patterns: Vec<String>,
+    /// Drop files matched by the repository's `.gitignore` rules.
+    pub respect_gitignore: bool,
+    /// Drop files marked `linguist-generated` or `linguist-vendored`.
+    pub respect_gitattributes: bool,
 }
 
 impl ScanlineConfig {
@@ -60,27 +200,120 @@ impl ScanlineConfig {
             allow_dirs: self.include_dirs.clone().unwrap_or_default(),
             extra_exclude_files: self.exclude_files.clone().unwrap_or_default(),
             extra_exclude_extensions: self.exclude_extensions.clone().unwrap_or_default(),
+            exclude_patterns: self.exclude_patterns.clone().unwrap_or_default(),
+            respect_gitignore: self.respect_gitignore.unwrap_or(false),
+            respect_gitattributes: self.respect_gitattributes.unwrap_or(false),
+        }
+    }
+
+    /// Extracts the `security:` section into a [`SecurityOverrides`] value.
+    pub fn security_overrides(&self) -> SecurityOverrides {
+        let Some(sec) = &self.security else {
+            return SecurityOverrides::default();
+        };
+        let allow = sec.allowlist.as_ref();
+        SecurityOverrides {
+            rules: sec.rules.clone().unwrap_or_default(),
+            allow_paths: allow.and_then(|a| a.paths.clone()).unwrap_or_default(),
+            allow_commits: allow.and_then(|a| a.commits.clone()).unwrap_or_default(),
         }
     }
 
+    /// Extracts the `boundaries:` section into a [`BoundaryOverrides`] value.
+    /// Malformed `allow` entries are rejected by [`Self::validate`].
+    pub fn boundary_overrides(&self) -> BoundaryOverrides {
+        let Some(b) = &self.boundaries else {
+            return BoundaryOverrides::default();
+        };
+        BoundaryOverrides {
+            components: b.components.clone().unwrap_or_default(),
+            allow: b
+                .allow
+                .iter()
+                .flatten()
+                .filter_map(|a| parse_allow(a))
+                .collect(),
+        }
+    }
+
+    /// Returns the `tests.rules` entries, or none.
+    pub fn test_rules(&self) -> Vec<TestRuleConfig> {
+        self.tests
+            .as_ref()
+            .and_then(|t| t.rules.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the `teams` mapping, or an empty one.
+    pub fn teams(&self) -> BTreeMap<String, Vec<String>> {
+        self.teams.clone().unwrap_or_default()
+    }
+
     /// Validates semantic constraints that serde cannot enforce.
     ///
     /// Returns a human-readable error describing exactly what is wrong and what
     /// values are accepted. Called automatically by [`load_config`].
     pub fn validate(&self) -> Result<(), String> {
-        // format must be one of the three supported output drivers
+        // format must be one of the supported output drivers
         if let Some(fmt) = &self.format {
             match fmt.as_str() {
-                "terminal" | "json" | "html" => {}
+                "terminal" | "json" | "html" | "markdown" | "csv" | "tsv" | "sqlite" | "dot"
+                | "mermaid" | "svg" => {}
                 other => {
                     return Err(format!(
                         "Invalid 'format' value: \"{other}\". \
-                         Expected one of: \"terminal\", \"json\", \"html\""
+                         Expected one of: \"terminal\", \"json\", \"html\", \"markdown\", \"csv\", \"tsv\", \"sqlite\", \"dot\", \"mermaid\", \"svg\""
                     ))
                 }
             }
         }
 
+        if let Some(n) = self.markdown_max_chars {
+            if n < crate::reporters::markdown::MIN_MAX_CHARS {
+                return Err(format!(
+                    "Invalid 'markdown_max_chars' value: {n}. Must be {} or greater",
+                    crate::reporters::markdown::MIN_MAX_CHARS
+                ));
+            }
+        }
+
+        if let Some(by) = &self.treemap_size {
+            if !matches!(by.as_str(), "churn" | "loc") {
+                return Err(format!(
+                    "Invalid 'treemap_size' value: \"{by}\". Expected one of: \"churn\", \"loc\""
+                ));
+            }
+        }
+
+        if let Some(g) = &self.graph {
+            if let Some(v) = g.min_strength {
+                if !(0.0..=100.0).contains(&v) {
+                    return Err(format!(
+                        "Invalid 'graph.min_strength' value: {v}. Must be between 0 and 100"
+                    ));
+                }
+            }
+            if let Some(n @ (0 | 1)) = g.max_nodes {
+                return Err(format!(
+                    "Invalid 'graph.max_nodes' value: {n}. Must be 2 or greater"
+                ));
+            }
+        }
+
+        if let Some(0) = self.stable_months {
+            return Err("Invalid 'stable_months' value: 0. Must be 1 or greater".to_string());
+        }
+        if let Some(0) = self.orphan_months {
+            return Err("Invalid 'orphan_months' value: 0. Must be 1 or greater".to_string());
+        }
+        if let Some(v) = self.cluster_strength {
+            if !(v > 0.0 && v <= 100.0) {
+                return Err(format!(
+                    "Invalid 'cluster_strength' value: {v}. Must be greater than 0 and at most 100"
+                ));
+            }
+        }
+
         // top: 0 would silently produce an empty report — almost certainly a mistake
         if let Some(0) = self.top {
             return Err("Invalid 'top' value: 0. \
@@ -99,6 +332,11 @@ impl ScanlineConfig {
                 ("coupling", w.coupling),
                 ("silo", w.silo),
                 ("commit_quality", w.commit_quality),
+                ("complexity", w.complexity),
+                ("tests", w.tests),
+                ("coverage", w.coverage),
+                ("entropy", w.entropy),
+                ("experience", w.experience),
             ];
             for (name, val) in fields {
                 if let Some(v) = val {
@@ -119,10 +357,123 @@ impl ScanlineConfig {
             }
         }
 
+        if let Some(patterns) = &self.exclude_patterns {
+            crate::filters::validate_patterns(patterns)
+                .map_err(|e| format!("Invalid 'exclude_patterns' entry: {e}"))?;
+        }
+
+        for (i, rule) in self.test_rules().iter().enumerate() {
+            if let Err(e) = regex::Regex::new(&rule.source) {
+                return Err(format!(
+                    "Invalid 'tests.rules[{i}].source' \"{}\": {e}",
+                    rule.source
+                ));
+            }
+            if rule.test.trim().is_empty() {
+                return Err(format!(
+                    "Invalid 'tests.rules[{i}].test': must not be empty"
+                ));
+            }
+        }
+
+        for (team, members) in self.teams.iter().flatten() {
+            if team.trim().is_empty() {
+                return Err("Invalid 'teams' entry: team name must not be empty".to_string());
+            }
+            if let Some(i) = members.iter().position(|m| m.trim().is_empty()) {
+                return Err(format!(
+                    "Invalid 'teams.{team}[{i}]': must be an email or '@domain'"
+                ));
+            }
+        }
+
+        if let Some(b) = &self.boundaries {
+            let components = b.components.as_deref().unwrap_or_default();
+            for (i, c) in components.iter().enumerate() {
+                if c.name.trim().is_empty() || c.name == "*" {
+                    return Err(format!(
+                        "Invalid 'boundaries.components[{i}].name': must be a non-empty name other than '*'"
+                    ));
+                }
+                if c.paths.is_empty() {
+                    return Err(format!(
+                        "Invalid 'boundaries.components[{i}].paths': list at least one glob"
+                    ));
+                }
+                for p in &c.paths {
+                    crate::filters::compile_glob(p).map_err(|e| {
+                        format!("Invalid 'boundaries.components[{i}].paths' entry: {e}")
+                    })?;
+                }
+            }
+            for (i, entry) in b.allow.iter().flatten().enumerate() {
+                let Some((from, to)) = parse_allow(entry) else {
+                    return Err(format!(
+                        "Invalid 'boundaries.allow[{i}]' \"{entry}\": expected \"component -> component\""
+                    ));
+                };
+                for side in [from, to] {
+                    if side != "*" && !components.iter().any(|c| c.name == side) {
+                        return Err(format!(
+                            "Invalid 'boundaries.allow[{i}]': unknown component \"{side}\""
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(sec) = &self.security {
+            for (i, rule) in sec.rules.iter().flatten().enumerate() {
+                if let Err(e) = regex::Regex::new(&rule.pattern) {
+                    return Err(format!(
+                        "Invalid 'security.rules[{i}].pattern' \"{}\": {e}",
+                        rule.pattern
+                    ));
+                }
+                if rule.risk_type.trim().is_empty() {
+                    return Err(format!(
+                        "Invalid 'security.rules[{i}].risk_type': must not be empty"
+                    ));
+                }
+                if let Some(sev) = &rule.severity {
+                    if !SEVERITIES.contains(&sev.as_str()) {
+                        return Err(format!(
+                            "Invalid 'security.rules[{i}].severity' value: \"{sev}\". \
+                             Expected one of: \"critical\", \"high\", \"medium\", \"low\""
+                        ));
+                    }
+                }
+            }
+            if let Some(allow) = &sec.allowlist {
+                for p in allow.paths.iter().flatten() {
+                    crate::filters::compile_glob(p)
+                        .map_err(|e| format!("Invalid 'security.allowlist.paths' entry: {e}"))?;
+                }
+                for c in allow.commits.iter().flatten() {
+                    if c.len() < 4 || !c.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                        return Err(format!(
+                            "Invalid 'security.allowlist.commits' entry: \"{c}\". \
+                             Expected a commit hash of at least 4 hex characters"
+                        ));
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Splits a `boundaries.allow` entry `"a -> b"` into `("a", "b")`.
+fn parse_allow(entry: &str) -> Option<(String, String)> {
+    let (from, to) = entry.split_once("->")?;
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() {
+        return None;
+    }
+    Some((from.to_string(), to.to_string()))
+}
+
 /// Reads, parses, and validates a YAML config file from `path`.
 pub fn load_config(path: &Path) -> Result<ScanlineConfig, String> {
     let content = std::fs::read_to_string(path)
@@ -160,14 +511,79 @@ pub static TEMPLATE: &str = r#"# git-scanline configuration file
 # Only show files that appear in bug-fix commits.
 # bugs_only: false
 
+# Report every scored file instead of the top N (useful with csv / tsv).
+# Line age, history and coupled files are still filled only for the top N.
+# Equivalent to --all-files.
+# all_files: false
+
+# Deep-scan the content of every line added in history for credentials
+# (AWS keys, GitHub/Slack tokens, private keys, high-entropy assignments).
+# Slower than the default filename-based scan. Equivalent to --scan-secrets.
+# scan_secrets: false
+
+# Also rank individual functions/methods (Rust, Go, Python, Java, JS/TS) using
+# the enclosing-function context of each changed hunk. Equivalent to --functions.
+# functions: false
+
+# LCOV, Cobertura or JaCoCo XML coverage report. Each hotspot gets its line
+# coverage, and the uncovered share feeds the 'coverage' weight.
+# Equivalent to --coverage.
+# coverage: "coverage/lcov.info"
+
+# Halve the score of files untouched for this many months — settled code is
+# rarely where the next bug comes from. Also the threshold for classifying files
+# as stable (twice this: abandoned). Without it, files are still classified
+# using 12 months, but no score is dampened. Equivalent to --stable-months.
+# stable_months: 12
+
+# Authors with no commits for this many months count as gone: files whose
+# primary authors have all gone are reported as orphaned. Equivalent to
+# --orphan-months.
+# orphan_months: 6
+
+# Files linked by co-change coupling of at least this strength (%) are grouped
+# into clusters of files that change together. Lower values give fewer, larger
+# clusters. Equivalent to --cluster-strength.
+# cluster_strength: 30
+
+# Replace author emails with stable pseudonyms (author-3f9a2c1b) in every
+# output format, e.g. to share reports externally. Scores are unchanged.
+# Keep the salt secret and unchanged to get the same pseudonyms across runs.
+# Equivalent to --anonymize / --anonymize-salt.
+# anonymize: true
+# anonymize_salt: "change-me"
+
 # ── Output ─────────────────────────────────────────────────────────────────────
 
-# Output format: terminal, json, html
+# Output format: terminal, json, html, markdown, csv, tsv, sqlite, dot, mermaid,
+# svg (markdown is for PR comments and wikis; csv / tsv write one row per file
+# for spreadsheets and pandas; sqlite appends each run to a database, by default
+# git-scanline.db, for trends over time; dot and mermaid draw the coupling graph,
+# e.g. for architecture docs; svg writes the hotspot treemap as an image)
 # format: "terminal"
 
+# Markdown size limit in characters: rows are dropped, least important first,
+# until the report fits. GitHub PR comments allow 65536.
+# Equivalent to --markdown-max-chars.
+# markdown_max_chars: 65000
+
 # Output file path. For HTML, defaults to ~/Desktop/hotspot-report.html
 # output: "hotspot-report.json"
 
+# Coupling graph (format: dot / mermaid). Edges below min_strength (%) are
+# dropped; the strongest edges are kept until max_nodes is reached. With
+# directories: true, files are merged into one node per directory.
+# Equivalent to --graph-min-strength / --graph-max-nodes / --graph-dirs.
+# graph:
+#   min_strength: 20
+#   max_nodes: 40
+#   directories: false
+
+# Treemap (HTML report and format: svg) rectangle area: "churn" (lines added
+# plus deleted in the analyzed history) or "loc" (lines of code at HEAD).
+# Equivalent to --treemap-size.
+# treemap_size: "churn"
+
 # ── File filtering ─────────────────────────────────────────────────────────────
 
 # Additional directories to exclude (merged with the built-in list).
@@ -194,6 +610,90 @@ pub static TEMPLATE: &str = r#"# git-scanline configuration file
 #   - ".generated.ts"
 #   - ".d.ts"
 
+# Gitignore-style glob patterns matched against the full repo-relative path.
+# Prefix with "!" to re-include a file, even one on a built-in list.
+# The last matching pattern wins. Patterns in a .scanlineignore file at the
+# repo root are applied after these. Equivalent to --exclude.
+# exclude_patterns:
+#   - "**/generated/**"
+#   - "src/**/*_pb2.py"
+#   - "!dist/sdk.js"
+
+# Also exclude files matched by the repository's .gitignore rules.
+# Equivalent to --respect-gitignore.
+# respect_gitignore: false
+
+# Also exclude files marked linguist-generated or linguist-vendored in
+# .gitattributes. Equivalent to --respect-gitattributes.
+# respect_gitattributes: false
+
+# Read candidate files at HEAD and exclude those that look generated
+# ("@generated" or "Code generated ... DO NOT EDIT." in the leading comments),
+# minified, or vendored (a third-party library banner as the first comment).
+# Excluded files are listed in the report. Equivalent to --detect-generated.
+# detect_generated: false
+
+# ── Security scan ──────────────────────────────────────────────────────────────
+
+# security:
+#   # Extra filename rules, checked before the built-ins (env-file, key-or-cert,
+#   # credential-file). pattern is a regex matched against the repo-relative path.
+#   # severity: critical, high, medium, low (default: high)
+#   rules:
+#     - pattern: '\.tfstate$'
+#       risk_type: "terraform-state"
+#       severity: "critical"
+#
+#   # Known-safe findings. Suppressed findings are counted in the report so
+#   # auditors can see what was waived.
+#   allowlist:
+#     paths:                      # exact paths or globs
+#       - ".env.example"
+#       - "test/fixtures/**"
+#     commits:                    # full or abbreviated hashes
+#       - "3f9a2c1"
+
+# ── Test pairing ───────────────────────────────────────────────────────────────
+
+# Source files are paired with tests by naming convention (foo_test.go,
+# test_foo.py, foo.spec.ts, FooTest.java, tests/foo.rs, ...). Add rules for
+# layouts the conventions miss. source is a regex on the repo-relative path;
+# test may use its capture groups ($1, ${name}).
+# tests:
+#   rules:
+#     - source: '^lib/(.*)\.rb$'
+#       test: "spec/unit/${1}_spec.rb"
+
+# ── Architectural boundaries ───────────────────────────────────────────────────
+
+# Files in different components that keep changing together reveal a hidden
+# dependency. Such pairs are reported as boundary violations and weigh 1.5×
+# in the coupling score, unless the dependency is listed under 'allow'.
+# A file belongs to the first component whose paths match; files matching
+# none are never part of a violation.
+# boundaries:
+#   components:
+#     - name: frontend
+#       paths: ["frontend/**"]
+#     - name: billing
+#       paths: ["billing-service/**"]
+#     - name: shared
+#       paths: ["libs/shared/**"]
+#   allow:
+#     - "* -> shared"         # everyone may depend on shared (either direction)
+
+# ── Teams ──────────────────────────────────────────────────────────────────────
+
+# Groups authors for `git-scanline authors --teams`. Members are author emails,
+# or '@domain' for everyone at that domain; exact emails win over domains.
+# Authors matching no team are listed as "(unassigned)".
+# teams:
+#   payments:
+#     - "alice@example.com"
+#     - "@payments.example.com"
+#   platform:
+#     - "bob@example.com"
+
 # ── Scoring weights ────────────────────────────────────────────────────────────
 # All weights are normalized at runtime so they always sum to 1.0.
 # Increase a weight to emphasize that signal; decrease to de-emphasize it.
@@ -206,6 +706,11 @@ pub static TEMPLATE: &str = r#"# git-scanline configuration file
 #   coupling:       0.09   # Files that always change together
 #   silo:           0.05   # Single-author concentration risk
 #   commit_quality: 0.09   # WIP and oversized commits
+#   complexity:     0.10   # Size and indentation depth of the file at HEAD (off unless set)
+#   tests:          0.10   # Changes without test changes (off unless set)
+#   coverage:       0.10   # Uncovered lines (only with a coverage report)
+#   entropy:        0.10   # Scattered, chaotic change periods (off unless set)
+#   experience:     0.10   # Changes by newcomers or authors new to the file (off unless set)
 "#;
 
 /// Prints the config template to stdout, or writes it to `output_path` if given.
@@ -334,7 +839,7 @@ mod tests {
 
     #[test]
     fn test_validate_invalid_format_rejected() {
-        let yaml = "format: \"csv\"\n";
+        let yaml = "format: \"xml\"\n";
         let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
         let result = cfg.validate();
         assert!(result.is_err(), "Invalid format should be rejected");
@@ -397,6 +902,11 @@ mod tests {
             "coupling",
             "silo",
             "commit_quality",
+            "complexity",
+            "tests",
+            "coverage",
+            "entropy",
+            "experience",
         ];
         for field in field_names {
             let yaml = format!("weights:\n  {field}: -1.0\n");
@@ -413,6 +923,184 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tests_rules_validated() {
+        let yaml =
+            "tests:\n  rules:\n    - source: '^lib/(.*)\\.rb$'\n      test: 'spec/${1}_spec.rb'\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.test_rules()[0].test, "spec/${1}_spec.rb");
+
+        let bad = "tests:\n  rules:\n    - source: '(unclosed'\n      test: 'x'\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(bad).expect("should parse");
+        let err = cfg.validate().unwrap_err();
+        assert!(
+            err.contains("tests.rules[0].source"),
+            "Error should name the rule: {err}"
+        );
+    }
+
+    #[test]
+    fn test_zero_month_periods_rejected() {
+        for field in ["stable_months", "orphan_months"] {
+            let cfg: ScanlineConfig =
+                serde_yaml::from_str(&format!("{field}: 0\n")).expect("should parse");
+            let err = cfg.validate().unwrap_err();
+            assert!(err.contains(field), "Error should name '{field}': {err}");
+        }
+    }
+
+    #[test]
+    fn test_cluster_strength_range() {
+        for (v, ok) in [("30", true), ("100", true), ("0", false), ("150", false)] {
+            let cfg: ScanlineConfig =
+                serde_yaml::from_str(&format!("cluster_strength: {v}\n")).expect("should parse");
+            assert_eq!(cfg.validate().is_ok(), ok, "cluster_strength: {v}");
+        }
+    }
+
+    #[test]
+    fn test_markdown_max_chars_minimum() {
+        for (v, ok) in [("65000", true), ("1000", true), ("999", false)] {
+            let cfg: ScanlineConfig =
+                serde_yaml::from_str(&format!("format: markdown\nmarkdown_max_chars: {v}\n"))
+                    .expect("should parse");
+            assert_eq!(cfg.validate().is_ok(), ok, "markdown_max_chars: {v}");
+        }
+    }
+
+    #[test]
+    fn test_treemap_size_values() {
+        for (v, ok) in [("churn", true), ("loc", true), ("bytes", false)] {
+            let cfg: ScanlineConfig =
+                serde_yaml::from_str(&format!("format: svg\ntreemap_size: {v}\n"))
+                    .expect("should parse");
+            assert_eq!(cfg.validate().is_ok(), ok, "treemap_size: {v}");
+        }
+    }
+
+    #[test]
+    fn test_graph_section_validated() {
+        let ok = "format: dot\ngraph:\n  min_strength: 25\n  max_nodes: 30\n  directories: true\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(ok).expect("should parse");
+        assert!(cfg.validate().is_ok());
+
+        for bad in ["graph:\n  min_strength: 120\n", "graph:\n  max_nodes: 1\n"] {
+            let cfg: ScanlineConfig = serde_yaml::from_str(bad).expect("should parse");
+            let err = cfg.validate().unwrap_err();
+            assert!(err.contains("graph."), "Error should name the field: {err}");
+        }
+    }
+
+    #[test]
+    fn test_boundaries_parsed_and_validated() {
+        let yaml = "boundaries:\n  components:\n    - name: web\n      paths: ['web/**']\n    \
+                    - name: api\n      paths: ['api/**']\n  allow:\n    - 'web -> api'\n    - '* -> web'\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_ok());
+        let b = cfg.boundary_overrides();
+        assert_eq!(b.components.len(), 2);
+        assert_eq!(b.allow[0], ("web".to_string(), "api".to_string()));
+
+        for (bad, field) in [
+            (
+                "boundaries:\n  allow: ['web => api']\n",
+                "boundaries.allow[0]",
+            ),
+            (
+                "boundaries:\n  allow: ['web -> api']\n",
+                "unknown component",
+            ),
+            (
+                "boundaries:\n  components:\n    - name: web\n      paths: []\n",
+                "boundaries.components[0].paths",
+            ),
+        ] {
+            let cfg: ScanlineConfig = serde_yaml::from_str(bad).expect("should parse");
+            let err = cfg.validate().unwrap_err();
+            assert!(err.contains(field), "Error should mention '{field}': {err}");
+        }
+    }
+
+    #[test]
+    fn test_teams_parsed_and_validated() {
+        let yaml = "teams:\n  payments:\n    - alice@example.com\n    - '@pay.example.com'\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.teams()["payments"].len(), 2);
+
+        let bad = "teams:\n  payments:\n    - ''\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(bad).expect("should parse");
+        let err = cfg.validate().unwrap_err();
+        assert!(
+            err.contains("teams.payments[0]"),
+            "Error should name the entry: {err}"
+        );
+    }
+
+    #[test]
+    fn test_security_section_parsed() {
+        let yaml = "security:\n  rules:\n    - pattern: '\\.tfstate$'\n      risk_type: tfstate\n  allowlist:\n    commits:\n      - abc1234\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_ok());
+        let so = cfg.security_overrides();
+        assert_eq!(so.rules[0].risk_type, "tfstate");
+        assert!(so.rules[0].severity.is_none());
+        assert_eq!(so.allow_commits, vec!["abc1234"]);
+    }
+
+    #[test]
+    fn test_validate_security_bad_severity_rejected() {
+        let yaml =
+            "security:\n  rules:\n    - pattern: x\n      risk_type: t\n      severity: urgent\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        let msg = cfg.validate().unwrap_err();
+        assert!(
+            msg.contains("severity"),
+            "Error should name the field: {msg}"
+        );
+        assert!(
+            msg.contains("critical"),
+            "Error should list valid values: {msg}"
+        );
+    }
+
+    #[test]
+    fn test_validate_security_bad_regex_rejected() {
+        let yaml = "security:\n  rules:\n    - pattern: '(unclosed'\n      risk_type: t\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        let msg = cfg.validate().unwrap_err();
+        assert!(
+            msg.contains("pattern"),
+            "Error should name the field: {msg}"
+        );
+    }
+
+    #[test]
+    fn test_validate_security_bad_commit_rejected() {
+        let yaml = "security:\n  allowlist:\n    commits:\n      - not-a-hash\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_err(), "Non-hex commit should be rejected");
+    }
+
+    #[test]
+    fn test_exclude_patterns_parsed_and_validated() {
+        let yaml = "exclude_patterns:\n  - \"**/generated/**\"\n  - \"!generated/keep.rs\"\nrespect_gitignore: true\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(yaml).expect("should parse");
+        assert!(cfg.validate().is_ok());
+        let fo = cfg.filter_overrides();
+        assert_eq!(fo.exclude_patterns.len(), 2);
+        assert!(fo.respect_gitignore);
+        assert!(!fo.respect_gitattributes);
+
+        let bad = "exclude_patterns:\n  - \"src/[z-a].rs\"\n";
+        let cfg: ScanlineConfig = serde_yaml::from_str(bad).expect("should parse");
+        assert!(
+            cfg.validate().is_err(),
+            "Invalid glob in exclude_patterns should be rejected"
+        );
+    }
+
     // ── Example file test ─────────────────────────────────────────────────────
 
     #[test]
@@ -526,10 +1214,28 @@ mod tests {
             "commit_quality weight should be 0.09"
         );
 
+        // Security
+        let so = cfg.security_overrides();
+        assert_eq!(so.rules.len(), 1, "example should define one custom rule");
+        assert_eq!(so.rules[0].risk_type, "terraform-state");
+        assert!(so.allow_paths.contains(&".env.example".to_string()));
+        assert!(so.allow_paths.contains(&"test/fixtures/**".to_string()));
+
+        assert_eq!(
+            cfg.test_rules().len(),
+            1,
+            "example should define one test rule"
+        );
+
         // Filter overrides roundtrip
         let fo = cfg.filter_overrides();
         assert!(fo.extra_exclude_dirs.contains(&"proto".to_string()));
         assert!(fo.allow_dirs.contains(&"dist".to_string()));
         assert!(fo.extra_exclude_extensions.contains(&".d.ts".to_string()));
+        assert!(fo.exclude_patterns.contains(&"!dist/sdk.js".to_string()));
+        assert!(
+            fo.respect_gitattributes,
+            "example enables respect_gitattributes"
+        );
     }
 }
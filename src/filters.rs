@@ -1,6 +1,12 @@
 use crate::config::FilterOverrides;
+use crate::git::ignore::{ignored_paths, linguist_excluded_paths};
 use once_cell::sync::Lazy;
+use regex::Regex;
 use std::collections::HashSet;
+use std::path::Path;
+
+/// Per-repository ignore file, using `.gitignore` syntax.
+pub const IGNORE_FILE: &str = ".scanlineignore";
 
 static EXCLUDED_DIRS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     HashSet::from([
@@ -98,6 +104,8 @@ pub fn filter_files(
         .map(String::as_str)
         .collect();
 
+    let patterns = PatternSet::new(&overrides.exclude_patterns);
+
     files
         .iter()
         .filter(|file| {
@@ -114,6 +122,11 @@ pub fn filter_files(
                 }
             }
 
+            // Explicit patterns win over the built-in lists in both directions.
+            if let Some(excluded) = patterns.verdict(f) {
+                return !excluded;
+            }
+
             let segments: Vec<&str> = f.split('/').collect();
             let filename = segments.last().unwrap_or(&"");
 
@@ -138,6 +151,154 @@ pub fn filter_files(
         .collect()
 }
 
+/// Drops files excluded by `.gitignore` or marked `linguist-generated` /
+/// `linguist-vendored` in `.gitattributes`, when enabled in `overrides`.
+///
+/// Files re-included by a `!pattern` in `exclude_patterns` are always kept.
+pub fn filter_git_excluded(
+    repo: &Path,
+    files: Vec<String>,
+    overrides: &FilterOverrides,
+) -> Result<Vec<String>, String> {
+    if !overrides.respect_gitignore && !overrides.respect_gitattributes {
+        return Ok(files);
+    }
+    let mut excluded: HashSet<String> = HashSet::new();
+    if overrides.respect_gitignore {
+        excluded.extend(ignored_paths(repo, &files)?);
+    }
+    if overrides.respect_gitattributes {
+        excluded.extend(linguist_excluded_paths(repo, &files)?);
+    }
+    let patterns = PatternSet::new(&overrides.exclude_patterns);
+    Ok(files
+        .into_iter()
+        .filter(|f| !excluded.contains(f) || patterns.verdict(f) == Some(false))
+        .collect())
+}
+
+/// Reads exclusion patterns from `<repo>/.scanlineignore`.
+///
+/// Blank lines and `#` comments are skipped. Returns an empty list when the
+/// file does not exist.
+pub fn load_ignore_file(repo: &Path) -> Result<Vec<String>, String> {
+    let path = repo.join(IGNORE_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Cannot read '{}': {e}", path.display())),
+    };
+    let patterns: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    validate_patterns(&patterns).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(patterns)
+}
+
+/// Checks that every pattern (with any leading `!` removed) compiles.
+pub fn validate_patterns(patterns: &[String]) -> Result<(), String> {
+    for p in patterns {
+        compile_glob(split_negation(p).0)?;
+    }
+    Ok(())
+}
+
+/// Ordered gitignore-style patterns where the last match wins.
+struct PatternSet {
+    /// `(glob, negated)` in declaration order.
+    rules: Vec<(Regex, bool)>,
+}
+
+impl PatternSet {
+    /// Patterns are validated on load, so any that fail to compile here are skipped.
+    fn new(patterns: &[String]) -> Self {
+        let rules = patterns
+            .iter()
+            .filter_map(|p| {
+                let (glob, negated) = split_negation(p);
+                compile_glob(glob).ok().map(|re| (re, negated))
+            })
+            .collect();
+        PatternSet { rules }
+    }
+
+    /// `Some(true)` if the path is excluded, `Some(false)` if it is explicitly
+    /// re-included by a `!pattern`, `None` if no pattern matches.
+    fn verdict(&self, path: &str) -> Option<bool> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(re, _)| re.is_match(path))
+            .map(|(_, negated)| !negated)
+    }
+}
+
+/// Splits `!pattern` into `("pattern", true)`. `\!` and `\#` escape a literal
+/// leading character, as in `.gitignore`.
+fn split_negation(pattern: &str) -> (&str, bool) {
+    if let Some(rest) = pattern.strip_prefix('!') {
+        return (rest, true);
+    }
+    if pattern.starts_with("\\!") || pattern.starts_with("\\#") {
+        return (&pattern[1..], false);
+    }
+    (pattern, false)
+}
+
+/// Compiles a glob pattern into an anchored regex matched against a
+/// repo-relative path.
+///
+/// - `*` matches within a single path segment, `?` matches one character
+/// - `**` matches across segments (`docs/**`, `**/fixtures/*.pem`)
+/// - `[abc]` character classes are passed through
+/// - A pattern without `/` matches the filename at any depth (`.env.example`)
+pub fn compile_glob(pattern: &str) -> Result<Regex, String> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let anchored = pattern.contains('/');
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let chars: Vec<char> = pattern.trim_start_matches('/').chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => match chars[i..].iter().position(|&c| c == ']') {
+                Some(end) => {
+                    let class: String = chars[i + 1..i + end].iter().collect();
+                    let class = match class.strip_prefix('!') {
+                        Some(negated) => format!("^{negated}"),
+                        None => class,
+                    };
+                    re.push('[');
+                    re.push_str(&class.replace('\\', "\\\\"));
+                    re.push(']');
+                    i += end + 1;
+                    continue;
+                }
+                None => re.push_str("\\["),
+            },
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    // A directory pattern also matches everything beneath it.
+    re.push_str("(?:/.*)?$");
+    Regex::new(&re).map_err(|e| format!("Invalid glob pattern '{pattern}': {e}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +311,10 @@ mod tests {
         FilterOverrides::default()
     }
 
+    fn glob_match(pattern: &str, path: &str) -> bool {
+        compile_glob(pattern).expect("valid glob").is_match(path)
+    }
+
     #[test]
     fn test_removes_package_json_and_locks() {
         let files = to_strings(&[
@@ -309,6 +474,91 @@ mod tests {
         );
     }
 
+    // ── Glob tests ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_glob_basename_matches_at_any_depth() {
+        assert!(glob_match(".env.example", ".env.example"));
+        assert!(glob_match(".env.example", "services/api/.env.example"));
+        assert!(!glob_match(".env.example", ".env"));
+        assert!(glob_match("*.pem", "certs/dev.pem"));
+    }
+
+    #[test]
+    fn test_glob_double_star() {
+        assert!(glob_match("test/fixtures/**", "test/fixtures/certs/a.pem"));
+        assert!(glob_match("**/generated/**", "src/generated/api.ts"));
+        assert!(glob_match("**/generated/**", "generated/api.ts"));
+        assert!(glob_match("src/**/*_pb2.py", "src/a/b/msg_pb2.py"));
+        assert!(glob_match("src/**/*_pb2.py", "src/msg_pb2.py"));
+        assert!(!glob_match("src/**/*_pb2.py", "lib/msg_pb2.py"));
+    }
+
+    #[test]
+    fn test_glob_single_star_stays_in_segment() {
+        assert!(glob_match("src/*.rs", "src/main.rs"));
+        assert!(!glob_match("src/*.rs", "src/nested/main.rs"));
+    }
+
+    #[test]
+    fn test_glob_directory_matches_contents() {
+        assert!(glob_match("docs/", "docs/readme.md"));
+        assert!(glob_match("vendor", "third_party/vendor/lib.c"));
+    }
+
+    // ── Pattern tests ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_exclude_patterns_with_globs() {
+        let files = to_strings(&[
+            "src/app.py",
+            "src/api/msg_pb2.py",
+            "web/generated/client.ts",
+        ]);
+        let overrides = FilterOverrides {
+            exclude_patterns: vec!["src/**/*_pb2.py".to_string(), "**/generated/**".to_string()],
+            ..Default::default()
+        };
+        let filtered = filter_files(&files, None, &overrides);
+        assert_eq!(filtered, vec!["src/app.py".to_string()]);
+    }
+
+    #[test]
+    fn test_negated_pattern_reincludes_file() {
+        let files = to_strings(&["gen/a.rs", "gen/keep.rs", "dist/app.js"]);
+        let overrides = FilterOverrides {
+            exclude_patterns: vec![
+                "gen/**".to_string(),
+                "!gen/keep.rs".to_string(),
+                "!dist/app.js".to_string(),
+            ],
+            ..Default::default()
+        };
+        let filtered = filter_files(&files, None, &overrides);
+        assert!(
+            !filtered.contains(&"gen/a.rs".to_string()),
+            "gen/a.rs should be excluded"
+        );
+        assert!(
+            filtered.contains(&"gen/keep.rs".to_string()),
+            "Later negation should win over an earlier exclusion"
+        );
+        assert!(
+            filtered.contains(&"dist/app.js".to_string()),
+            "Negation should override the built-in dist/ exclusion"
+        );
+    }
+
+    #[test]
+    fn test_split_negation_and_escapes() {
+        assert_eq!(split_negation("!a/b"), ("a/b", true));
+        assert_eq!(
+            split_negation("\\!important.txt"),
+            ("!important.txt", false)
+        );
+        assert_eq!(split_negation("plain"), ("plain", false));
+    }
+
     #[test]
     fn test_default_overrides_unchanged_behaviour() {
         // Confirm FilterOverrides::default() produces identical results to no override
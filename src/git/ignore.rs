@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Returns which of `paths` match the repository's `.gitignore` rules
+/// (including nested `.gitignore` files and `.git/info/exclude`).
+///
+/// Uses `git check-ignore --no-index`, so files that were committed before
+/// being ignored — and files that no longer exist — are still matched.
+pub fn ignored_paths(cwd: &Path, paths: &[String]) -> Result<HashSet<String>, String> {
+    // check-ignore exits 1 when no path is ignored; that is not an error.
+    let out = run_with_stdin(
+        cwd,
+        &["check-ignore", "--no-index", "--stdin", "-z"],
+        paths,
+        &[0, 1],
+    )?;
+    Ok(out
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect())
+}
+
+/// Returns which of `paths` are marked `linguist-generated` or
+/// `linguist-vendored` in `.gitattributes`.
+pub fn linguist_excluded_paths(cwd: &Path, paths: &[String]) -> Result<HashSet<String>, String> {
+    let out = run_with_stdin(
+        cwd,
+        &[
+            "check-attr",
+            "--stdin",
+            "-z",
+            "linguist-generated",
+            "linguist-vendored",
+        ],
+        paths,
+        &[0],
+    )?;
+    Ok(parse_check_attr(&out))
+}
+
+/// Parses `git check-attr -z` output (`path NUL attr NUL value NUL ...`) and
+/// returns the paths where any requested attribute is set.
+fn parse_check_attr(out: &str) -> HashSet<String> {
+    let fields: Vec<&str> = out.split('\0').collect();
+    fields
+        .chunks_exact(3)
+        .filter(|f| matches!(f[2], "set" | "true"))
+        .map(|f| f[0].to_string())
+        .collect()
+}
+
+/// Runs git with `paths` fed NUL-separated on stdin and returns stdout.
+fn run_with_stdin(
+    cwd: &Path,
+    args: &[&str],
+    paths: &[String],
+    ok_codes: &[i32],
+) -> Result<String, String> {
+    if paths.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to capture git stdin".to_string())?;
+    let mut input: Vec<u8> = Vec::new();
+    for p in paths {
+        input.extend_from_slice(p.as_bytes());
+        input.push(0);
+    }
+    // Write from a separate thread so git can never block us on a full pipe.
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(&input);
+    });
+
+    let mut stdout = String::new();
+    child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture git stdout".to_string())?
+        .read_to_string(&mut stdout)
+        .map_err(|e| format!("Failed reading git output: {e}"))?;
+    let _ = writer.join();
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for git process: {e}"))?;
+    if !ok_codes.contains(&output.status.code().unwrap_or(-1)) {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_check_attr_keeps_set_attributes() {
+        let out = "gen/api.ts\0linguist-generated\0set\0\
+                   gen/api.ts\0linguist-vendored\0unspecified\0\
+                   src/app.ts\0linguist-generated\0unspecified\0\
+                   src/app.ts\0linguist-vendored\0unspecified\0\
+                   lib/x.js\0linguist-vendored\0true\0\
+                   lib/y.js\0linguist-generated\0false\0";
+        let paths = parse_check_attr(out);
+        assert!(paths.contains("gen/api.ts"), "set attribute should match");
+        assert!(paths.contains("lib/x.js"), "=true should match");
+        assert!(!paths.contains("src/app.ts"), "unspecified must not match");
+        assert!(!paths.contains("lib/y.js"), "=false must not match");
+    }
+}
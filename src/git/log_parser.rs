@@ -1,4 +1,5 @@
 use crate::types::{Commit, DiffStats, DiffStatsMap};
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -14,6 +15,53 @@ pub fn parse_log(
     cwd: &Path,
     since: &str,
     path_filter: Option<&str>,
+) -> Result<(Vec<Commit>, DiffStatsMap), String> {
+    read_log(cwd, since, path_filter, |_, _, _, _| {})
+}
+
+/// Lines added and deleted in one file by one commit; `None` for binary files.
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub file: String,
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// Each commit's [`FileChange`]s, keyed by commit hash.
+#[cfg(feature = "sqlite")]
+pub type CommitChanges = HashMap<String, Vec<FileChange>>;
+
+/// [`parse_log`] that also keeps each commit's per-file numstat, keyed by
+/// commit hash, from the same pass. Only the SQLite export needs it.
+#[cfg(feature = "sqlite")]
+pub fn parse_log_with_changes(
+    cwd: &Path,
+    since: &str,
+    path_filter: Option<&str>,
+) -> Result<(Vec<Commit>, DiffStatsMap, CommitChanges), String> {
+    let mut changes = CommitChanges::new();
+    let (commits, diff_stats) = read_log(cwd, since, path_filter, |hash, file, added, deleted| {
+        changes
+            .entry(hash.to_string())
+            .or_default()
+            .push(FileChange {
+                file: file.to_string(),
+                additions: added.parse().ok(),
+                deletions: deleted.parse().ok(),
+            });
+    })?;
+    Ok((commits, diff_stats, changes))
+}
+
+/// Runs the `git log --numstat` behind [`parse_log`], handing every numstat
+/// line to `on_change` as `(hash, file, added, deleted)`; binary files report
+/// `-` for both counts.
+fn read_log(
+    cwd: &Path,
+    since: &str,
+    path_filter: Option<&str>,
+    mut on_change: impl FnMut(&str, &str, &str, &str),
 ) -> Result<(Vec<Commit>, DiffStatsMap), String> {
     let mut args: Vec<String> = vec![
         "log".into(),
@@ -62,7 +110,13 @@ pub fn parse_log(
 
     for line in BufReader::new(stdout).lines() {
         let line = line.map_err(|e| format!("Failed reading git output: {e}"))?;
-        parse_commit_line(&line, &mut commits, &mut diff_stats, &mut current);
+        parse_commit_line(
+            &line,
+            &mut commits,
+            &mut diff_stats,
+            &mut current,
+            &mut on_change,
+        );
     }
 
     if let Some(c) = current.take() {
@@ -83,11 +137,73 @@ pub fn parse_log(
     Ok((commits, diff_stats))
 }
 
+/// Hashes of the commits that added and deleted a path, across all refs.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileLifecycle {
+    pub added_in: Vec<String>,
+    pub removed_in: Vec<String>,
+}
+
+/// Finds every commit, on any branch or tag, that added or deleted one of
+/// `paths`. Renames are reported as a delete plus an add.
+pub fn file_lifecycles(
+    cwd: &Path,
+    paths: &[String],
+) -> Result<HashMap<String, FileLifecycle>, String> {
+    let mut lifecycles: HashMap<String, FileLifecycle> = HashMap::new();
+    for chunk in paths.chunks(200) {
+        let mut args: Vec<&str> = vec![
+            "log",
+            "--all",
+            "--format=COMMIT|%H",
+            "--name-status",
+            "--no-renames",
+            "--diff-filter=AD",
+            "--",
+        ];
+        args.extend(chunk.iter().map(String::as_str));
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(cwd)
+            .output()
+            .map_err(|e| format!("Failed to run git: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        parse_name_status(&String::from_utf8_lossy(&output.stdout), &mut lifecycles);
+    }
+    Ok(lifecycles)
+}
+
+fn parse_name_status(text: &str, lifecycles: &mut HashMap<String, FileLifecycle>) {
+    let mut hash = "";
+    for line in text.lines() {
+        if let Some(h) = line.strip_prefix("COMMIT|") {
+            hash = h.trim();
+            continue;
+        }
+        let mut parts = line.splitn(2, '\t');
+        let (Some(status), Some(path)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let entry = lifecycles.entry(path.trim().to_string()).or_default();
+        match status {
+            "A" => entry.added_in.push(hash.to_string()),
+            "D" => entry.removed_in.push(hash.to_string()),
+            _ => {}
+        }
+    }
+}
+
 fn parse_commit_line(
     line: &str,
     commits: &mut Vec<Commit>,
     diff_stats: &mut DiffStatsMap,
     current: &mut Option<Commit>,
+    on_change: &mut impl FnMut(&str, &str, &str, &str),
 ) {
     let trimmed = line.trim();
 
@@ -123,6 +239,7 @@ fn parse_commit_line(
                     entry.deletions += deletions;
                 }
                 if let Some(ref mut c) = current {
+                    on_change(&c.hash, &filename, added_raw, deleted_raw);
                     c.files.push(filename);
                 }
             }
@@ -156,3 +273,64 @@ fn normalize_filename(raw: &str) -> Option<String> {
 
 static RENAME_RE: once_cell::sync::Lazy<regex::Regex> =
     once_cell::sync::Lazy::new(|| regex::Regex::new(r"\{[^}]+ => ([^}]+)\}").unwrap());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_name_status_collects_adds_and_deletes() {
+        let text =
+            "COMMIT|c3\n\nD\t.env\nCOMMIT|c2\n\nA\t.env\nA\tcerts/dev.pem\nCOMMIT|c1\n\nA\t.env\n";
+        let mut lifecycles = HashMap::new();
+        parse_name_status(text, &mut lifecycles);
+        assert_eq!(lifecycles[".env"].added_in, vec!["c2", "c1"]);
+        assert_eq!(lifecycles[".env"].removed_in, vec!["c3"]);
+        assert_eq!(lifecycles["certs/dev.pem"].added_in, vec!["c2"]);
+        assert!(lifecycles["certs/dev.pem"].removed_in.is_empty());
+    }
+
+    #[test]
+    fn test_numstat_lines_reported_per_commit() {
+        let text = "COMMIT|c2|ann@x|200|fix\n\n3\t1\tsrc/{old => new}/a.rs\n-\t-\tlogo.png\nCOMMIT|c1|bo@x|100|add\n\n10\t0\tREADME.md\n";
+        let (mut commits, mut stats, mut current) = (Vec::new(), DiffStatsMap::new(), None);
+        let mut seen = Vec::new();
+        for line in text.lines() {
+            parse_commit_line(
+                line,
+                &mut commits,
+                &mut stats,
+                &mut current,
+                &mut |hash: &str, file: &str, added: &str, deleted: &str| {
+                    seen.push(format!("{hash} {file} {added} {deleted}"));
+                },
+            );
+        }
+        assert_eq!(
+            seen,
+            vec![
+                "c2 src/new/a.rs 3 1",
+                "c2 logo.png - -",
+                "c1 README.md 10 0"
+            ]
+        );
+        assert_eq!(stats["src/new/a.rs"].additions, 3);
+        assert!(
+            !stats.contains_key("logo.png"),
+            "Binary files have no line stats"
+        );
+    }
+
+    #[test]
+    fn test_normalize_filename_renames() {
+        assert_eq!(
+            normalize_filename("src/{old => new}/file.js"),
+            Some("src/new/file.js".to_string())
+        );
+        assert_eq!(
+            normalize_filename("old-name => new-name"),
+            Some("new-name".to_string())
+        );
+        assert_eq!(normalize_filename("  "), None);
+    }
+}
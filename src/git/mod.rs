@@ -1 +1,4 @@
+pub mod ignore;
 pub mod log_parser;
+pub mod patch_parser;
+pub mod tree;
@@ -0,0 +1,440 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// A single line added to a file by a commit, as seen in `git log -p` output.
+#[derive(Debug, Clone)]
+pub struct AddedLine<'a> {
+    pub commit: &'a str,
+    pub author: &'a str,
+    pub timestamp: i64,
+    pub file: &'a str,
+    /// 1-based line number in the post-image of the commit.
+    pub line_no: usize,
+    pub text: &'a str,
+}
+
+/// One hunk of a commit, with the enclosing-function context git prints after
+/// the `@@ ... @@` header.
+#[derive(Debug, Clone)]
+pub struct Hunk<'a> {
+    pub commit: &'a str,
+    pub timestamp: i64,
+    pub subject: &'a str,
+    pub file: &'a str,
+    /// Text after the closing `@@`, e.g. `fn parse_log(cwd: &Path) {`. Empty
+    /// when git found no function line above the hunk.
+    pub context: &'a str,
+}
+
+/// Streaming state carried between `git log -p` output lines.
+#[derive(Debug, Default)]
+struct PatchState {
+    commit: String,
+    author: String,
+    timestamp: i64,
+    file: Option<String>,
+    next_line: usize,
+    header: DiffHeader,
+}
+
+/// Tracks whether the stream is in the header of a `diff --git` block, where
+/// `+++ ` names the target file. Inside a hunk the same prefix is an added line
+/// whose content starts with `++ `.
+#[derive(Debug, Default)]
+struct DiffHeader {
+    active: bool,
+}
+
+impl DiffHeader {
+    /// Feeds one line and returns the rest of it if it is the `+++ ` header.
+    fn target<'l>(&mut self, line: &'l str) -> Option<&'l str> {
+        if line.starts_with("diff --git ") {
+            self.active = true;
+        } else if line.starts_with("@@ ") {
+            self.active = false;
+        }
+        if self.active {
+            line.strip_prefix("+++ ")
+        } else {
+            None
+        }
+    }
+}
+
+/// Runs `git log -p --unified=0` and calls `on_line` for every added line in
+/// history. Output is streamed, so memory use does not grow with history size.
+///
+/// Only additions and modifications are considered — deleted lines cannot
+/// introduce anything new into the repository.
+pub fn for_each_added_line<F>(
+    cwd: &Path,
+    since: &str,
+    path_filter: Option<&str>,
+    mut on_line: F,
+) -> Result<(), String>
+where
+    F: FnMut(&AddedLine),
+{
+    let mut args: Vec<String> = vec![
+        "log".into(),
+        "-p".into(),
+        "--unified=0".into(),
+        "--no-color".into(),
+        "--no-ext-diff".into(),
+        "--format=COMMIT|%H|%ae|%ad".into(),
+        "--date=unix".into(),
+        "--diff-filter=AM".into(),
+    ];
+
+    if !since.is_empty() {
+        args.push(format!("--since={since}"));
+    }
+
+    if let Some(p) = path_filter {
+        args.push("--".into());
+        args.push(p.into());
+    }
+
+    let mut state = PatchState::default();
+    stream_git_lines(cwd, &args, |line| {
+        if let Some(added) = parse_patch_line(line, &mut state) {
+            on_line(&added);
+        }
+    })
+}
+
+/// Runs `git log -p --unified=0` and calls `on_hunk` once per hunk, carrying
+/// the function context from the hunk header.
+///
+/// `git_config` is passed as `-c key=value` pairs before the subcommand, which
+/// lets callers install diff drivers (`core.attributesFile`, `diff.<driver>.xfuncname`).
+pub fn for_each_hunk<F>(
+    cwd: &Path,
+    since: &str,
+    path_filter: Option<&str>,
+    git_config: &[String],
+    mut on_hunk: F,
+) -> Result<(), String>
+where
+    F: FnMut(&Hunk),
+{
+    let mut args: Vec<String> = Vec::new();
+    for kv in git_config {
+        args.push("-c".into());
+        args.push(kv.clone());
+    }
+    args.extend([
+        "log".into(),
+        "-p".into(),
+        "--unified=0".into(),
+        "--no-color".into(),
+        "--no-ext-diff".into(),
+        "--no-merges".into(),
+        "--format=COMMIT|%H|%ad|%s".into(),
+        "--date=unix".into(),
+    ]);
+    if !since.is_empty() {
+        args.push(format!("--since={since}"));
+    }
+    if let Some(p) = path_filter {
+        args.push("--".into());
+        args.push(p.into());
+    }
+
+    let mut state = HunkState::default();
+    stream_git_lines(cwd, &args, |line| {
+        if let Some(hunk) = parse_hunk_line(line, &mut state) {
+            on_hunk(&hunk);
+        }
+    })
+}
+
+/// Spawns git with `args` and feeds each stdout line (without the newline,
+/// decoded lossily) to `on_line`.
+fn stream_git_lines<F>(cwd: &Path, args: &[String], mut on_line: F) -> Result<(), String>
+where
+    F: FnMut(&str),
+{
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture git stdout".to_string())?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "Failed to capture git stderr".to_string())?;
+
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr_text = String::new();
+        let mut reader = BufReader::new(stderr);
+        let _ = reader.read_to_string(&mut stderr_text);
+        stderr_text
+    });
+
+    let mut reader = BufReader::new(stdout);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        buf.clear();
+        let n = reader
+            .read_until(b'\n', &mut buf)
+            .map_err(|e| format!("Failed reading git output: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        // Patches may contain arbitrary bytes; decode lossily instead of failing.
+        let line = String::from_utf8_lossy(&buf);
+        on_line(line.trim_end_matches(['\n', '\r']));
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for git process: {e}"))?;
+
+    if !status.success() {
+        let stderr_text = stderr_reader.join().unwrap_or_else(|_| String::new());
+        return Err(format!("git log -p failed: {stderr_text}"));
+    }
+
+    let _ = stderr_reader.join();
+    Ok(())
+}
+
+fn parse_patch_line<'a>(line: &'a str, state: &'a mut PatchState) -> Option<AddedLine<'a>> {
+    if let Some(rest) = line.strip_prefix("COMMIT|") {
+        let mut parts = rest.splitn(3, '|');
+        if let (Some(hash), Some(author), Some(ts)) = (parts.next(), parts.next(), parts.next()) {
+            state.commit = hash.to_string();
+            state.author = author.to_string();
+            state.timestamp = ts.trim().parse().unwrap_or(0);
+        }
+        state.file = None;
+        return None;
+    }
+    if let Some(rest) = state.header.target(line) {
+        state.file = parse_target_path(rest);
+        return None;
+    }
+    if line.starts_with("diff --git ") {
+        state.file = None;
+        return None;
+    }
+    if let Some(rest) = line.strip_prefix("@@ ") {
+        state.next_line = parse_hunk_start(rest).unwrap_or(0);
+        return None;
+    }
+    let text = line.strip_prefix('+')?;
+    let file = state.file.as_deref()?;
+    let line_no = state.next_line;
+    state.next_line += 1;
+    Some(AddedLine {
+        commit: &state.commit,
+        author: &state.author,
+        timestamp: state.timestamp,
+        file,
+        line_no,
+        text,
+    })
+}
+
+/// Streaming state for [`for_each_hunk`].
+#[derive(Debug, Default)]
+struct HunkState {
+    commit: String,
+    timestamp: i64,
+    subject: String,
+    file: Option<String>,
+    header: DiffHeader,
+}
+
+fn parse_hunk_line<'a>(line: &'a str, state: &'a mut HunkState) -> Option<Hunk<'a>> {
+    if let Some(rest) = line.strip_prefix("COMMIT|") {
+        let mut parts = rest.splitn(3, '|');
+        state.commit = parts.next().unwrap_or_default().to_string();
+        state.timestamp = parts.next().unwrap_or_default().trim().parse().unwrap_or(0);
+        state.subject = parts.next().unwrap_or_default().to_string();
+        state.file = None;
+        return None;
+    }
+    if let Some(rest) = state.header.target(line) {
+        state.file = parse_target_path(rest);
+        return None;
+    }
+    if line.starts_with("diff --git ") {
+        state.file = None;
+        return None;
+    }
+    let rest = line.strip_prefix("@@ ")?;
+    let context = rest.split_once(" @@").map_or("", |(_, c)| c.trim());
+    Some(Hunk {
+        commit: &state.commit,
+        timestamp: state.timestamp,
+        subject: &state.subject,
+        file: state.file.as_deref()?,
+        context,
+    })
+}
+
+/// Extracts the repository path from a `+++ b/path` header.
+/// Returns `None` for `/dev/null` (deleted files).
+fn parse_target_path(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw == "/dev/null" {
+        return None;
+    }
+    // git quotes paths containing special characters: "b/some file.rs"
+    let unquoted = raw.trim_matches('"');
+    Some(unquoted.strip_prefix("b/").unwrap_or(unquoted).to_string())
+}
+
+/// Parses the post-image start line from a hunk header body: `-a,b +c,d @@ ...`.
+fn parse_hunk_start(rest: &str) -> Option<usize> {
+    let plus = rest.split_whitespace().find(|p| p.starts_with('+'))?;
+    plus[1..].split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(lines: &[&str]) -> Vec<(String, usize, String)> {
+        let mut state = PatchState::default();
+        let mut out = Vec::new();
+        for line in lines {
+            if let Some(a) = parse_patch_line(line, &mut state) {
+                out.push((a.file.to_string(), a.line_no, a.text.to_string()));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_added_lines_carry_file_and_line_number() {
+        let out = collect(&[
+            "COMMIT|abc123|dev@example.com|1700000000",
+            "diff --git a/src/app.ts b/src/app.ts",
+            "--- a/src/app.ts",
+            "+++ b/src/app.ts",
+            "@@ -10,0 +11,2 @@ function main() {",
+            "+const a = 1;",
+            "+const b = 2;",
+        ]);
+        assert_eq!(out.len(), 2);
+        assert_eq!(
+            out[0],
+            ("src/app.ts".to_string(), 11, "const a = 1;".to_string())
+        );
+        assert_eq!(out[1].1, 12, "Line numbers should advance per added line");
+    }
+
+    #[test]
+    fn test_removed_lines_and_headers_are_ignored() {
+        let out = collect(&[
+            "COMMIT|abc123|dev@example.com|1700000000",
+            "diff --git a/a.rs b/a.rs",
+            "--- a/a.rs",
+            "+++ b/a.rs",
+            "@@ -3 +3 @@",
+            "-old line",
+            "+new line",
+        ]);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].2, "new line");
+    }
+
+    #[test]
+    fn test_added_line_starting_with_plus_plus_is_not_a_header() {
+        let out = collect(&[
+            "COMMIT|abc123|dev@example.com|1700000000",
+            "diff --git a/a.c b/a.c",
+            "--- a/a.c",
+            "+++ b/a.c",
+            "@@ -1,0 +1,2 @@",
+            "+++ counter;",
+            "+token = 1;",
+        ]);
+        assert_eq!(
+            out,
+            vec![
+                ("a.c".to_string(), 1, "++ counter;".to_string()),
+                ("a.c".to_string(), 2, "token = 1;".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deleted_file_target_is_skipped() {
+        assert_eq!(parse_target_path("/dev/null"), None);
+        assert_eq!(
+            parse_target_path("\"b/dir/some file.rs\""),
+            Some("dir/some file.rs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hunks_carry_function_context_and_subject() {
+        let mut state = HunkState::default();
+        let mut out = Vec::new();
+        for line in [
+            "COMMIT|abc123|1700000000|fix: handle | in subjects",
+            "diff --git a/src/lib.rs b/src/lib.rs",
+            "+++ b/src/lib.rs",
+            "@@ -10,2 +10,3 @@ pub fn parse(input: &str) -> Result<(), String> {",
+            "+    let x = 1;",
+            "@@ -40 +41 @@",
+        ] {
+            if let Some(h) = parse_hunk_line(line, &mut state) {
+                out.push((
+                    h.file.to_string(),
+                    h.context.to_string(),
+                    h.subject.to_string(),
+                ));
+            }
+        }
+        assert_eq!(out.len(), 2, "One entry per hunk header");
+        assert_eq!(
+            out[0].1,
+            "pub fn parse(input: &str) -> Result<(), String> {"
+        );
+        assert_eq!(
+            out[0].2, "fix: handle | in subjects",
+            "Subject may contain '|'"
+        );
+        assert_eq!(out[1].1, "", "Hunk without context has empty context");
+    }
+
+    #[test]
+    fn test_hunk_after_added_plus_plus_line_keeps_file() {
+        let mut state = HunkState::default();
+        let mut files = Vec::new();
+        for line in [
+            "COMMIT|abc123|1700000000|bump counter",
+            "diff --git a/a.c b/a.c",
+            "--- a/a.c",
+            "+++ b/a.c",
+            "@@ -1,0 +1 @@ int main() {",
+            "+++ counter;",
+            "@@ -9 +10 @@ void tick() {",
+        ] {
+            if let Some(h) = parse_hunk_line(line, &mut state) {
+                files.push(h.file.to_string());
+            }
+        }
+        assert_eq!(files, vec!["a.c", "a.c"]);
+    }
+
+    #[test]
+    fn test_parse_hunk_start() {
+        assert_eq!(parse_hunk_start("-1,3 +7,4 @@ fn x()"), Some(7));
+        assert_eq!(parse_hunk_start("-0,0 +1 @@"), Some(1));
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Reads the contents of `files` as they exist at HEAD using a single
+/// `git cat-file --batch` subprocess.
+///
+/// Files that do not exist at HEAD (deleted or renamed away) are simply absent
+/// from the returned map. Contents are decoded lossily so binary files never
+/// cause an error.
+pub fn read_head_files(cwd: &Path, files: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut contents: HashMap<String, String> = HashMap::new();
+    for_each_head_file(cwd, files, |file, content| {
+        contents.insert(file.to_string(), content.to_string());
+    })?;
+    Ok(contents)
+}
+
+/// Like [`read_head_files`], but hands each file's content to `visit` as it
+/// is read instead of keeping it, so only one file is held in memory at a time.
+pub fn for_each_head_file(
+    cwd: &Path,
+    files: &[String],
+    mut visit: impl FnMut(&str, &str),
+) -> Result<(), String> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to capture git stdin".to_string())?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture git stdout".to_string())?;
+
+    // Feed requests from a separate thread so a large response can never
+    // block us while we are still writing the request list.
+    let requests: Vec<String> = files.iter().map(|f| format!("HEAD:{f}\n")).collect();
+    let writer = thread::spawn(move || {
+        for req in requests {
+            if stdin.write_all(req.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut reader = BufReader::new(stdout);
+    for file in files {
+        let mut header = String::new();
+        let n = reader
+            .read_line(&mut header)
+            .map_err(|e| format!("Failed reading git output: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        // "<sha> <type> <size>" on success, "<name> missing" otherwise
+        let parts: Vec<&str> = header.trim_end().split(' ').collect();
+        if parts.len() != 3 || parts[2].parse::<usize>().is_err() {
+            continue;
+        }
+        let size: usize = parts[2].parse().unwrap_or(0);
+        let mut buf = vec![0u8; size + 1]; // trailing newline after each object
+        reader
+            .read_exact(&mut buf)
+            .map_err(|e| format!("Failed reading git output: {e}"))?;
+        buf.truncate(size);
+        if parts[1] == "blob" {
+            visit(file, &String::from_utf8_lossy(&buf));
+        }
+    }
+
+    let _ = writer.join();
+    let _ = child.wait();
+    Ok(())
+}
+
+/// Lists local branch and tag names (`main`, `v1.2.0`).
+pub fn list_refs(cwd: &Path) -> Result<Vec<String>, String> {
+    let out = run_git(
+        cwd,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)",
+            "refs/heads",
+            "refs/tags",
+        ],
+    )?;
+    Ok(out.lines().map(|l| l.trim().to_string()).collect())
+}
+
+/// Returns which of `paths` exist as files in the tree of each of `revs`
+/// (commits, branches or tags), keyed by rev. Every rev × path pair is checked
+/// by a single `git cat-file --batch-check`, so thousands of tags cost one
+/// process. Revs containing none of the paths are left out.
+pub fn paths_in_revs(
+    cwd: &Path,
+    revs: &[String],
+    paths: &[String],
+) -> Result<HashMap<String, HashSet<String>>, String> {
+    let mut found: HashMap<String, HashSet<String>> = HashMap::new();
+    // One request per line, so a path with a newline cannot be asked for.
+    let paths: Vec<&String> = paths.iter().filter(|p| !p.contains('\n')).collect();
+    if revs.is_empty() || paths.is_empty() {
+        return Ok(found);
+    }
+
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch-check=%(objecttype)"])
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to capture git stdin".to_string())?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture git stdout".to_string())?;
+
+    let requests: Vec<String> = revs
+        .iter()
+        .flat_map(|rev| paths.iter().map(move |p| format!("{rev}:{p}\n")))
+        .collect();
+    let writer = thread::spawn(move || {
+        for req in requests {
+            if stdin.write_all(req.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Answers come back in request order: the object type, or "<name> missing".
+    let mut lines = BufReader::new(stdout).lines();
+    for rev in revs {
+        for path in &paths {
+            let line = lines
+                .next()
+                .ok_or_else(|| "git cat-file ended early".to_string())?
+                .map_err(|e| format!("Failed reading git output: {e}"))?;
+            if line == "blob" {
+                found
+                    .entry(rev.clone())
+                    .or_default()
+                    .insert(path.to_string());
+            }
+        }
+    }
+
+    let _ = writer.join();
+    let _ = child.wait();
+    Ok(found)
+}
+
+/// Lists every file path in the tree of `rev`.
+pub fn list_tree(cwd: &Path, rev: &str) -> Result<Vec<String>, String> {
+    let out = run_git(cwd, &["ls-tree", "-r", "--name-only", "-z", rev])?;
+    Ok(out
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect())
+}
+
+/// Returns `(committer time, line count)` for each blame hunk of `file` at HEAD.
+pub fn blame_line_times(cwd: &Path, file: &str) -> Result<Vec<(i64, usize)>, String> {
+    let out = run_git(cwd, &["blame", "--incremental", "HEAD", "--", file])?;
+    Ok(parse_blame_incremental(&out))
+}
+
+/// Parses `git blame --incremental`: each hunk starts with
+/// `<sha> <orig-line> <final-line> <count>`; commit headers such as
+/// `committer-time` follow only the first hunk of each commit.
+fn parse_blame_incremental(out: &str) -> Vec<(i64, usize)> {
+    let mut times: HashMap<&str, i64> = HashMap::new();
+    // (sha, line count) in output order; times resolved at the end.
+    let mut hunks: Vec<(&str, usize)> = Vec::new();
+    let mut current: Option<&str> = None;
+    for line in out.lines() {
+        let parts: Vec<&str> = line.split(' ').collect();
+        if parts.len() == 4
+            && parts[0].len() == 40
+            && parts[0].bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            let count = parts[3].parse().unwrap_or(0);
+            hunks.push((parts[0], count));
+            current = Some(parts[0]);
+        } else if let (Some(sha), Some(t)) = (current, line.strip_prefix("committer-time ")) {
+            if let Ok(t) = t.parse() {
+                times.insert(sha, t);
+            }
+        }
+    }
+    hunks
+        .into_iter()
+        .filter_map(|(sha, count)| times.get(sha).map(|&t| (t, count)))
+        .collect()
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blame_incremental_reuses_commit_headers() {
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+        let out = format!(
+            "{a} 1 1 3\nauthor x\ncommitter-time 100\nfilename f.rs\n\
+             {b} 4 4 1\ncommitter-time 200\nfilename f.rs\n\
+             {a} 5 5 2\nfilename f.rs\n"
+        );
+        assert_eq!(
+            parse_blame_incremental(&out),
+            vec![(100, 3), (200, 1), (100, 2)],
+            "Later hunks of a commit reuse its committer-time"
+        );
+    }
+}
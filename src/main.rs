@@ -1,5 +1,6 @@
 mod analyzers;
 mod animation;
+mod anonymize;
 mod config;
 mod filters;
 mod git;
@@ -7,9 +8,9 @@ mod reporters;
 mod scoring;
 mod types;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -27,63 +28,205 @@ use types::*;
                   Tip: Drag any folder onto this executable to analyze it."
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to a git repository OR a parent folder containing multiple repos.
     #[arg(value_name = "PATH")]
     repo_path: Option<PathBuf>,
 
     /// Leave empty (default) to include all history, or e.g. "6 months ago", "2024-01-01"
-    #[arg(long, default_value = "")]
+    #[arg(long, default_value = "", global = true)]
     since: String,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     path: Option<String>,
 
-    #[arg(long, default_value_t = 20)]
+    #[arg(long, default_value_t = 20, global = true)]
     top: usize,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     bugs_only: bool,
 
-    /// Output format: terminal, json, html
-    #[arg(long, default_value = "terminal")]
+    /// Report every scored file instead of the top N (useful with csv / tsv);
+    /// line age, history and coupled files are still filled only for the top N
+    #[arg(long, global = true)]
+    all_files: bool,
+
+    /// Deep-scan the content of every line added in history for credentials
+    /// (API keys, tokens, private keys). Slower than the default filename scan.
+    #[arg(long, global = true)]
+    scan_secrets: bool,
+
+    /// Also rank functions/methods by churn, bug-fix and revert history
+    /// (Rust, Go, Python, Java, JavaScript, TypeScript)
+    #[arg(long, global = true)]
+    functions: bool,
+
+    /// LCOV, Cobertura or JaCoCo XML coverage report to weight untested hotspots
+    #[arg(long, value_name = "FILE", global = true)]
+    coverage: Option<PathBuf>,
+
+    /// Halve the score of files untouched for this many months; also the
+    /// threshold for classifying files as stable (default 12)
+    #[arg(long, value_name = "MONTHS", global = true)]
+    stable_months: Option<u32>,
+
+    /// Treat authors with no commits for this many months as gone when
+    /// reporting orphaned files (default 6)
+    #[arg(long, value_name = "MONTHS", global = true)]
+    orphan_months: Option<u32>,
+
+    /// Minimum coupling strength (%) linking files into a co-change
+    /// cluster (default 30)
+    #[arg(long, value_name = "PERCENT", global = true)]
+    cluster_strength: Option<f64>,
+
+    /// Exclude files matching a gitignore-style glob (repeatable).
+    /// Prefix with "!" to re-include, e.g. --exclude '**/generated/**' --exclude '!gen/keep.rs'
+    #[arg(long = "exclude", value_name = "GLOB", global = true)]
+    exclude: Vec<String>,
+
+    /// Also exclude files matched by the repository's .gitignore rules
+    #[arg(long, global = true)]
+    respect_gitignore: bool,
+
+    /// Also exclude files marked linguist-generated or linguist-vendored in .gitattributes
+    #[arg(long, global = true)]
+    respect_gitattributes: bool,
+
+    /// Inspect files at HEAD and exclude generated, minified and vendored code
+    #[arg(long, global = true)]
+    detect_generated: bool,
+
+    /// Output format: terminal, json, html, markdown, csv, tsv, sqlite, dot, mermaid, svg
+    /// (dot and mermaid draw the coupling graph; svg the hotspot treemap;
+    /// sqlite appends the run to a database, git-scanline.db by default)
+    #[arg(long, default_value = "terminal", global = true)]
     format: String,
 
+    /// Coupling graph (dot, mermaid): minimum coupling strength (%) for an edge (default 20)
+    #[arg(long, value_name = "PERCENT", global = true)]
+    graph_min_strength: Option<f64>,
+
+    /// Coupling graph (dot, mermaid): maximum number of nodes (default 40)
+    #[arg(long, value_name = "N", global = true)]
+    graph_max_nodes: Option<usize>,
+
+    /// Coupling graph (dot, mermaid): one node per directory instead of per file
+    #[arg(long, global = true)]
+    graph_dirs: bool,
+
+    /// Markdown: drop the least important rows until the report fits in N characters
+    /// (GitHub PR comments allow 65536)
+    #[arg(long, value_name = "N", global = true)]
+    markdown_max_chars: Option<usize>,
+
+    /// Treemap (html, svg) rectangle area: churn (lines changed) or loc (lines at HEAD)
+    #[arg(long, value_name = "BY", value_parser = ["churn", "loc"], global = true)]
+    treemap_size: Option<String>,
+
     /// Output file (single repo). For multiple repos, repo names are appended automatically.
-    /// For --format html, defaults to ~/Desktop/hotspot-report.html
-    #[arg(long)]
+    /// For --format html, defaults to ~/Desktop/hotspot-report.html. For csv / tsv, a
+    /// directory (existing, or ending in /) also gets couplings and security_risks tables
+    #[arg(long, global = true)]
     output: Option<PathBuf>,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_interactive: bool,
 
+    /// Replace author emails with stable pseudonyms (author-3f9a2c1b) in every output format
+    #[arg(long, global = true)]
+    anonymize: bool,
+
+    /// Secret salt for --anonymize pseudonyms; keep it to get the same names across runs
+    #[arg(long, value_name = "SALT", global = true)]
+    anonymize_salt: Option<String>,
+
     /// Path to a YAML configuration file.
     /// Run `git-scanline --generate-config` to print an annotated template.
-    #[arg(long, value_name = "FILE")]
+    #[arg(long, value_name = "FILE", global = true)]
     config: Option<PathBuf>,
 
     /// Print a .git-scanline.yml config template to stdout and exit.
     /// Example: git-scanline --generate-config > .git-scanline.yml
-    #[arg(long)]
+    #[arg(long, global = true)]
     generate_config: bool,
 
-    #[arg(long = "weight-churn", default_value_t = 0.27)]
+    #[arg(long = "weight-churn", default_value_t = 0.27, global = true)]
     weight_churn: f64,
-    #[arg(long = "weight-bugs", default_value_t = 0.27)]
+    #[arg(long = "weight-bugs", default_value_t = 0.27, global = true)]
     weight_bugs: f64,
-    #[arg(long = "weight-reverts", default_value_t = 0.14)]
+    #[arg(long = "weight-reverts", default_value_t = 0.14, global = true)]
     weight_reverts: f64,
-    #[arg(long = "weight-bursts", default_value_t = 0.09)]
+    #[arg(long = "weight-bursts", default_value_t = 0.09, global = true)]
     weight_bursts: f64,
-    #[arg(long = "weight-coupling", default_value_t = 0.09)]
+    #[arg(long = "weight-coupling", default_value_t = 0.09, global = true)]
     weight_coupling: f64,
-    #[arg(long = "weight-silo", default_value_t = 0.05)]
+    #[arg(long = "weight-silo", default_value_t = 0.05, global = true)]
     weight_silo: f64,
-    #[arg(long = "weight-commit-quality", default_value_t = 0.09)]
+    #[arg(long = "weight-commit-quality", default_value_t = 0.09, global = true)]
     weight_commit_quality: f64,
+    /// Weight for size and indentation depth of the file at HEAD (off by default)
+    #[arg(long = "weight-complexity", default_value_t = 0.0, global = true)]
+    weight_complexity: f64,
+    /// Weight for source changes that did not touch the file's tests (off by default)
+    #[arg(long = "weight-tests", default_value_t = 0.0, global = true)]
+    weight_tests: f64,
+    /// Weight for uncovered lines; only applies with --coverage
+    #[arg(long = "weight-coverage", default_value_t = 0.10, global = true)]
+    weight_coverage: f64,
+    /// Weight for change entropy, Hassan's scattered-change signal (off by default)
+    #[arg(long = "weight-entropy", default_value_t = 0.0, global = true)]
+    weight_entropy: f64,
+    /// Weight for changes by newcomers or authors new to the file (off by default)
+    #[arg(long = "weight-experience", default_value_t = 0.0, global = true)]
+    weight_experience: f64,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Per-author contribution report: files touched, hotspots owned, bug
+    /// fixes, reverts, WIP ratio and commit size
+    Authors {
+        /// Path to a git repository OR a parent folder containing multiple repos.
+        #[arg(value_name = "PATH")]
+        repo_path: Option<PathBuf>,
+
+        /// Group authors into the teams defined under `teams` in the config file
+        #[arg(long)]
+        teams: bool,
+    },
+    /// Files that usually change together with FILE, ranked by confidence
+    /// (how often a change to FILE also touched them)
+    Coupled {
+        /// File to query, relative to the repository root
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// Path to the git repository (default: current directory)
+        #[arg(value_name = "PATH")]
+        repo_path: Option<PathBuf>,
+
+        /// Count commits by the same author within this many hours as one change set
+        #[arg(long, value_name = "HOURS")]
+        window_hours: Option<u32>,
+
+        /// Only list files that changed together with FILE at least this often
+        #[arg(long, value_name = "N", default_value_t = 2)]
+        min_support: usize,
+    },
 }
 
 fn main() {
     let mut args = Args::parse();
+    if let Some(Command::Authors { repo_path, .. } | Command::Coupled { repo_path, .. }) =
+        &mut args.command
+    {
+        if args.repo_path.is_none() {
+            args.repo_path = repo_path.take();
+        }
+    }
 
     // ── --generate-config: print template and exit ────────────────────────────
     if args.generate_config {
@@ -127,11 +270,105 @@ fn main() {
             args.bugs_only = v;
         }
     }
+    if !args.all_files {
+        if let Some(v) = cfg.all_files {
+            args.all_files = v;
+        }
+    }
+    if !args.scan_secrets {
+        if let Some(v) = cfg.scan_secrets {
+            args.scan_secrets = v;
+        }
+    }
+    if !args.functions {
+        if let Some(v) = cfg.functions {
+            args.functions = v;
+        }
+    }
+    if args.coverage.is_none() {
+        args.coverage = cfg.coverage.as_ref().map(PathBuf::from);
+    }
+    if args.stable_months.is_none() {
+        args.stable_months = cfg.stable_months;
+    }
+    if args.orphan_months.is_none() {
+        args.orphan_months = cfg.orphan_months;
+    }
+    if args.cluster_strength.is_none() {
+        args.cluster_strength = cfg.cluster_strength;
+    }
+    if !args.anonymize {
+        if let Some(v) = cfg.anonymize {
+            args.anonymize = v;
+        }
+    }
+    if args.anonymize_salt.is_none() {
+        args.anonymize_salt = cfg.anonymize_salt.clone();
+    }
+    if args.anonymize && args.anonymize_salt.is_none() {
+        eprintln!(
+            "Note: --anonymize without --anonymize-salt uses a public default salt; \
+             anyone who knows an email can recompute its pseudonym."
+        );
+    }
+    if !args.detect_generated {
+        if let Some(v) = cfg.detect_generated {
+            args.detect_generated = v;
+        }
+    }
     if args.format == "terminal" {
         if let Some(v) = cfg.format.as_deref() {
             args.format = v.to_string();
         }
     }
+    if let Some(g) = &cfg.graph {
+        if args.graph_min_strength.is_none() {
+            args.graph_min_strength = g.min_strength;
+        }
+        if args.graph_max_nodes.is_none() {
+            args.graph_max_nodes = g.max_nodes;
+        }
+        if !args.graph_dirs {
+            if let Some(v) = g.directories {
+                args.graph_dirs = v;
+            }
+        }
+    }
+    if args.treemap_size.is_none() {
+        args.treemap_size = cfg.treemap_size.clone();
+    }
+    if args.markdown_max_chars.is_none() {
+        args.markdown_max_chars = cfg.markdown_max_chars;
+    }
+    if let Some(n) = args.markdown_max_chars {
+        if n < reporters::markdown::MIN_MAX_CHARS {
+            eprintln!(
+                "Error: --markdown-max-chars must be {} or greater",
+                reporters::markdown::MIN_MAX_CHARS
+            );
+            std::process::exit(1);
+        }
+    }
+    if args.format == "sqlite" && !cfg!(feature = "sqlite") {
+        eprintln!(
+            "Error: --format sqlite is not available in this build; \
+             rebuild with the 'sqlite' cargo feature"
+        );
+        std::process::exit(1);
+    }
+    if args.command.is_some()
+        && matches!(
+            args.format.as_str(),
+            "markdown" | "csv" | "tsv" | "sqlite" | "dot" | "mermaid" | "svg"
+        )
+    {
+        eprintln!(
+            "Error: --format {} is only available for the hotspot report; \
+             subcommands support terminal, json and html",
+            args.format
+        );
+        std::process::exit(1);
+    }
     if args.output.is_none() {
         if let Some(v) = &cfg.output {
             args.output = Some(PathBuf::from(v));
@@ -173,8 +410,49 @@ fn main() {
                 args.weight_commit_quality = v;
             }
         }
+        if args.weight_complexity == 0.0 {
+            if let Some(v) = w.complexity {
+                args.weight_complexity = v;
+            }
+        }
+        if args.weight_tests == 0.0 {
+            if let Some(v) = w.tests {
+                args.weight_tests = v;
+            }
+        }
+        if args.weight_coverage == 0.10 {
+            if let Some(v) = w.coverage {
+                args.weight_coverage = v;
+            }
+        }
+        if args.weight_entropy == 0.0 {
+            if let Some(v) = w.entropy {
+                args.weight_entropy = v;
+            }
+        }
+        if args.weight_experience == 0.0 {
+            if let Some(v) = w.experience {
+                args.weight_experience = v;
+            }
+        }
+    }
+    let mut filter_overrides = cfg.filter_overrides();
+    if let Err(e) = filters::validate_patterns(&args.exclude) {
+        eprintln!("Error: invalid --exclude pattern: {e}");
+        std::process::exit(1);
+    }
+    filter_overrides.respect_gitignore |= args.respect_gitignore;
+    filter_overrides.respect_gitattributes |= args.respect_gitattributes;
+    let security_overrides = cfg.security_overrides();
+    let test_rules = cfg.test_rules();
+    let boundaries = cfg.boundary_overrides();
+    let teams = cfg.teams();
+    if matches!(args.command, Some(Command::Authors { teams: true, .. })) && teams.is_empty() {
+        eprintln!(
+            "Error: --teams needs a 'teams' mapping in the config file (see --generate-config)"
+        );
+        std::process::exit(1);
     }
-    let filter_overrides = cfg.filter_overrides();
 
     let explicit_args = std::env::args().len() > 1;
     let run_interactive_mode = args.repo_path.is_none() && !args.no_interactive && !explicit_args;
@@ -234,6 +512,16 @@ fn main() {
             coupling: args.weight_coupling,
             silo: args.weight_silo,
             commit_quality: args.weight_commit_quality,
+            complexity: args.weight_complexity,
+            tests: args.weight_tests,
+            // Without a report every gap is zero; keep it out of the normalization.
+            coverage: if args.coverage.is_some() {
+                args.weight_coverage
+            } else {
+                0.0
+            },
+            entropy: args.weight_entropy,
+            experience: args.weight_experience,
         };
         let wsum = raw.churn
             + raw.bugs
@@ -241,7 +529,12 @@ fn main() {
             + raw.bursts
             + raw.coupling
             + raw.silo
-            + raw.commit_quality;
+            + raw.commit_quality
+            + raw.complexity
+            + raw.tests
+            + raw.coverage
+            + raw.entropy
+            + raw.experience;
         let weights = Weights {
             churn: raw.churn / wsum,
             bugs: raw.bugs / wsum,
@@ -250,6 +543,11 @@ fn main() {
             coupling: raw.coupling / wsum,
             silo: raw.silo / wsum,
             commit_quality: raw.commit_quality / wsum,
+            complexity: raw.complexity / wsum,
+            tests: raw.tests / wsum,
+            coverage: raw.coverage / wsum,
+            entropy: raw.entropy / wsum,
+            experience: raw.experience / wsum,
         };
 
         // ── Base output path (used for single repo or as template for multi) ─────
@@ -259,6 +557,11 @@ fn main() {
                     .unwrap_or_else(|| PathBuf::from("."))
                     .join("hotspot-report.html")
             })),
+            "sqlite" => Some(
+                args.output
+                    .clone()
+                    .unwrap_or_else(|| PathBuf::from("git-scanline.db")),
+            ),
             _ => args.output.clone(),
         };
 
@@ -271,7 +574,12 @@ fn main() {
 
             // Resolve output path for this repo
             let output_path = base_output.as_deref().map(|base| {
-                if is_multi {
+                // Every repo's run goes into the one database.
+                if args.format == "sqlite" {
+                    base.to_path_buf()
+                } else if is_multi && reporters::csv::is_dir_output(base) {
+                    base.join(repo_name)
+                } else if is_multi {
                     make_output_path(base, repo_name)
                 } else {
                     base.to_path_buf()
@@ -290,6 +598,10 @@ fn main() {
                 &args,
                 &weights,
                 &filter_overrides,
+                &security_overrides,
+                &test_rules,
+                &boundaries,
+                &teams,
                 output_path.as_deref(),
                 is_multi,
                 run_interactive_mode,
@@ -327,6 +639,10 @@ fn run_analysis(
     args: &Args,
     weights: &Weights,
     filter_overrides: &config::FilterOverrides,
+    security_overrides: &config::SecurityOverrides,
+    test_rules: &[config::TestRuleConfig],
+    boundaries: &config::BoundaryOverrides,
+    teams: &BTreeMap<String, Vec<String>>,
     output_path: Option<&Path>,
     is_multi: bool,
     interactive_mode: bool,
@@ -349,63 +665,241 @@ fn run_analysis(
     let mut step_start = Instant::now();
 
     pb.set_message(format!("{}[1/5] Parsing commit log + diff stats...", pfx));
-    let (commits, diff_stats) =
-        match git::log_parser::parse_log(repo_path, &args.since, args.path.as_deref()) {
-            Ok((c, _)) if c.is_empty() => {
-                pb.finish_and_clear();
-                return Err(format!(
-                    "No commits found in '{}'. Try --since=\"4 years ago\"",
-                    repo_path.display()
-                ));
-            }
-            Ok((c, d)) => (c, d),
-            Err(e) => {
-                pb.finish_and_clear();
-                return Err(e.to_string());
-            }
-        };
+    // The SQLite export stores per-commit numstat, kept from this same pass.
+    #[cfg(feature = "sqlite")]
+    let (parsed, commit_changes) = if args.format == "sqlite" {
+        match git::log_parser::parse_log_with_changes(repo_path, &args.since, args.path.as_deref())
+        {
+            Ok((c, d, changes)) => (Ok((c, d)), changes),
+            Err(e) => (Err(e), Default::default()),
+        }
+    } else {
+        let parsed = git::log_parser::parse_log(repo_path, &args.since, args.path.as_deref());
+        (parsed, Default::default())
+    };
+    #[cfg(not(feature = "sqlite"))]
+    let parsed = git::log_parser::parse_log(repo_path, &args.since, args.path.as_deref());
+    let (commits, diff_stats) = match parsed {
+        Ok((c, _)) if c.is_empty() => {
+            pb.finish_and_clear();
+            return Err(format!(
+                "No commits found in '{}'. Try --since=\"4 years ago\"",
+                repo_path.display()
+            ));
+        }
+        Ok((c, d)) => (c, d),
+        Err(e) => {
+            pb.finish_and_clear();
+            return Err(e.to_string());
+        }
+    };
     let t1 = fmt_dur(step_start.elapsed());
     step_start = Instant::now();
     pb.println(format!(
         "  ✓ [1/5] Parsing commit log + diff stats       {t1}"
     ));
 
-    pb.set_message(format!("{}[2/5] Scanning for security risks...", pfx));
-    let security_risks = analyzers::security::analyze_security(&commits);
-    let t2 = fmt_dur(step_start.elapsed());
-    step_start = Instant::now();
-    pb.println(format!(
-        "  ✓ [2/5] Scanning for security risks           {t2}"
-    ));
-
-    pb.set_message(format!("{}[3/5] Filtering files...", pfx));
+    pb.set_message(format!("{}[2/5] Filtering files...", pfx));
     let all_files: HashSet<String> = commits
         .iter()
         .flat_map(|c| c.files.iter().cloned())
         .collect();
+    // Pattern order is config → .scanlineignore → --exclude, so the CLI wins.
+    let mut repo_overrides = filter_overrides.clone();
+    match filters::load_ignore_file(repo_path) {
+        Ok(patterns) => repo_overrides.exclude_patterns.extend(patterns),
+        Err(e) => {
+            pb.finish_and_clear();
+            return Err(e);
+        }
+    }
+    repo_overrides
+        .exclude_patterns
+        .extend(args.exclude.iter().cloned());
     let filtered_files = filters::filter_files(
         &all_files.into_iter().collect::<Vec<_>>(),
         args.path.as_deref(),
-        filter_overrides,
+        &repo_overrides,
     );
+    let mut filtered_files =
+        match filters::filter_git_excluded(repo_path, filtered_files, &repo_overrides) {
+            Ok(f) => f,
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e);
+            }
+        };
+    if let Some(Command::Coupled {
+        file,
+        window_hours,
+        min_support,
+        ..
+    }) = &args.command
+    {
+        pb.finish_and_clear();
+        let target = repo_relative(repo_path, file);
+        let change_sets = match window_hours {
+            Some(h) => analyzers::coupling::group_change_sets(&commits, *h),
+            None => commits.clone(),
+        };
+        let mut files = filtered_files.clone();
+        if !files.contains(&target) {
+            files.push(target.clone());
+        }
+        let (target_sets, mut coupled) =
+            analyzers::coupling::query_coupling(&change_sets, &files, &target, *min_support);
+        if target_sets == 0 {
+            return Err(format!(
+                "'{target}' has no commits in the analyzed history. \
+                 Paths are relative to the repository root."
+            ));
+        }
+        coupled.truncate(args.top);
+        let report = CouplingQuery {
+            meta: ReportMeta {
+                since: if args.since.is_empty() {
+                    "all history".to_string()
+                } else {
+                    args.since.clone()
+                },
+                commit_count: commits.len(),
+                file_count: filtered_files.len(),
+                analyzed_at: chrono::Utc::now().to_rfc3339(),
+                repo_path: repo_path.display().to_string(),
+            },
+            file: target,
+            change_sets: target_sets,
+            window_hours: *window_hours,
+            coupled,
+        };
+        match args.format.as_str() {
+            "json" => reporters::json::report_json(&report, output_path)?,
+            "html" => {
+                let path = output_path.ok_or("output path required for html")?;
+                reporters::html::report_coupled_html(&report, path)?;
+            }
+            _ => reporters::terminal::report_coupled_terminal(&report),
+        }
+        return Ok(());
+    }
+
+    // Each file's content at HEAD is read once, measured and dropped: only its
+    // complexity metrics, inline-test marker and generated verdict are kept.
+    let mut measured: HashMap<String, ComplexityData> = HashMap::new();
+    let mut inline_tests: HashSet<String> = HashSet::new();
+    let mut auto_excluded: Vec<GeneratedFile> = Vec::new();
+    let read = git::tree::for_each_head_file(repo_path, &filtered_files, |file, content| {
+        if args.detect_generated {
+            if let Some(found) = analyzers::generated::detect_generated(file, content) {
+                auto_excluded.push(found);
+                return;
+            }
+        }
+        measured.insert(file.to_string(), analyzers::complexity::measure(content));
+        if analyzers::test_coevolution::has_inline_tests(content) {
+            inline_tests.insert(file.to_string());
+        }
+    });
+    if let Err(e) = read {
+        pb.finish_and_clear();
+        return Err(e);
+    }
+    // Every path at HEAD, so tests untouched in the window can still be paired.
+    let head_paths = match git::tree::list_tree(repo_path, "HEAD") {
+        Ok(p) => p,
+        Err(e) => {
+            pb.finish_and_clear();
+            return Err(e);
+        }
+    };
+    auto_excluded.sort_by(|a, b| a.file.cmp(&b.file));
+    let drop: HashSet<&str> = auto_excluded.iter().map(|g| g.file.as_str()).collect();
+    filtered_files.retain(|f| !drop.contains(f.as_str()));
     if filtered_files.is_empty() {
         pb.finish_and_clear();
         return Err("No files found after filtering. Try --path or --since.".to_string());
     }
+    let coverage_data = match &args.coverage {
+        Some(report) => match analyzers::coverage::analyze_coverage(report, &filtered_files) {
+            Ok(c) => c,
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e);
+            }
+        },
+        None => Default::default(),
+    };
+    let t2 = fmt_dur(step_start.elapsed());
+    step_start = Instant::now();
+    pb.println(format!(
+        "  ✓ [2/5] Filtering files                       {t2}"
+    ));
+
+    pb.set_message(format!("{}[3/5] Scanning for security risks...", pfx));
+    let (mut security_risks, suppressed_risks) =
+        analyzers::security::analyze_security(&commits, security_overrides);
+    if let Err(e) = analyzers::security::locate_risks(repo_path, &mut security_risks) {
+        pb.println(format!(
+            "  ⚠  Could not locate security risks in branches and tags: {e}"
+        ));
+    }
+    let (secret_findings, suppressed_secrets) = if args.scan_secrets {
+        match analyzers::secrets::scan_history(repo_path, &args.since, args.path.as_deref()) {
+            Ok(f) => analyzers::security::suppress_secret_findings(f, security_overrides),
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e);
+            }
+        }
+    } else {
+        (Vec::new(), 0)
+    };
     let t3 = fmt_dur(step_start.elapsed());
     step_start = Instant::now();
     pb.println(format!(
-        "  ✓ [3/5] Filtering files                       {t3}"
+        "  ✓ [3/5] Scanning for security risks           {t3}"
     ));
 
     pb.set_message(format!("{}[4/5] Running all analyzers in parallel...", pfx));
+    let stable_days = args
+        .stable_months
+        .map_or(analyzers::code_age::DEFAULT_STABLE_DAYS, |m| {
+            i64::from(m) * 30
+        });
+    let orphan_days = args
+        .orphan_months
+        .map_or(analyzers::blame::DEFAULT_ORPHAN_DAYS, |m| i64::from(m) * 30);
     let (
-        (churn_data, (bug_data, revert_data)),
-        (burst_data, (coupling_data, (silo_data, commit_quality_data))),
+        (
+            (churn_data, (complexity_data, (entropy_data, entropy_periods))),
+            (bug_data, (revert_data, age_data)),
+        ),
+        (
+            (burst_data, (test_data, experience_data)),
+            (
+                mut coupling_data,
+                ((silo_data, (mut ownership_data, knowledge_risks)), commit_quality_data),
+            ),
+        ),
     ) = rayon::join(
         || {
             rayon::join(
-                || analyzers::churn::analyze_churn(&commits, &filtered_files),
+                || {
+                    rayon::join(
+                        || analyzers::churn::analyze_churn(&commits, &filtered_files),
+                        || {
+                            rayon::join(
+                                || {
+                                    analyzers::complexity::analyze_complexity(
+                                        &measured,
+                                        &filtered_files,
+                                    )
+                                },
+                                || analyzers::entropy::analyze_entropy(&commits, &filtered_files),
+                            )
+                        },
+                    )
+                },
                 || {
                     rayon::join(
                         || {
@@ -414,20 +908,75 @@ fn run_analysis(
                                 &filtered_files,
                             )
                         },
-                        || analyzers::revert_tracker::analyze_reverts(&commits, &filtered_files),
+                        || {
+                            rayon::join(
+                                || {
+                                    analyzers::revert_tracker::analyze_reverts(
+                                        &commits,
+                                        &filtered_files,
+                                    )
+                                },
+                                || {
+                                    analyzers::code_age::analyze_code_age(
+                                        &commits,
+                                        &filtered_files,
+                                        stable_days,
+                                    )
+                                },
+                            )
+                        },
                     )
                 },
             )
         },
         || {
             rayon::join(
-                || analyzers::burst_detector::analyze_bursts(&commits, &filtered_files),
+                || {
+                    rayon::join(
+                        || analyzers::burst_detector::analyze_bursts(&commits, &filtered_files),
+                        || {
+                            rayon::join(
+                                || {
+                                    analyzers::test_coevolution::analyze_test_coevolution(
+                                        &commits,
+                                        &filtered_files,
+                                        &head_paths,
+                                        &inline_tests,
+                                        test_rules,
+                                    )
+                                },
+                                || {
+                                    analyzers::experience::analyze_experience(
+                                        &commits,
+                                        &filtered_files,
+                                    )
+                                },
+                            )
+                        },
+                    )
+                },
                 || {
                     rayon::join(
                         || analyzers::coupling::analyze_coupling(&commits, &filtered_files),
                         || {
                             rayon::join(
-                                || analyzers::blame::analyze_authors(&commits, &filtered_files),
+                                || {
+                                    rayon::join(
+                                        || {
+                                            analyzers::blame::analyze_authors(
+                                                &commits,
+                                                &filtered_files,
+                                            )
+                                        },
+                                        || {
+                                            analyzers::blame::analyze_turnover(
+                                                &commits,
+                                                &filtered_files,
+                                                orphan_days,
+                                            )
+                                        },
+                                    )
+                                },
                                 || {
                                     analyzers::commit_quality::analyze_commit_quality(
                                         &commits,
@@ -441,13 +990,35 @@ fn run_analysis(
             )
         },
     );
+    let function_hotspots = if args.functions {
+        pb.set_message(format!("{}[4/5] Attributing changes to functions...", pfx));
+        match analyzers::functions::analyze_functions(
+            repo_path,
+            &args.since,
+            args.path.as_deref(),
+            &filtered_files,
+            weights,
+        ) {
+            Ok(mut f) => {
+                f.truncate(args.top);
+                f
+            }
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e);
+            }
+        }
+    } else {
+        Vec::new()
+    };
     let t4 = fmt_dur(step_start.elapsed());
     step_start = Instant::now();
     pb.println(format!(
-        "  ✓ [4/5] All 7 analyzers (parallel)            {t4}"
+        "  ✓ [4/5] All 13 analyzers (parallel)           {t4}"
     ));
 
     pb.set_message(format!("{}[5/5] Scoring hotspots...", pfx));
+    analyzers::coupling::classify_boundaries(&mut coupling_data, boundaries);
     let mut results = scoring::score_hotspots(
         &filtered_files,
         &churn_data,
@@ -457,6 +1028,13 @@ fn run_analysis(
         &coupling_data,
         &silo_data,
         &commit_quality_data,
+        &complexity_data,
+        &test_data,
+        &coverage_data,
+        &entropy_data,
+        &age_data,
+        &experience_data,
+        args.stable_months.map(|_| stable_days),
         &diff_stats,
         weights,
     );
@@ -464,7 +1042,43 @@ fn run_analysis(
         results.retain(|r| r.details.bug_commits > 0);
     }
 
-    if args.top == 0 {
+    // The graph colors every coupled file, not only the reported ones.
+    let graph_format = matches!(args.format.as_str(), "dot" | "mermaid");
+    let file_tiers: HashMap<String, Tier> = if graph_format {
+        results
+            .iter()
+            .map(|r| (r.file.clone(), r.tier.clone()))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let size_by = match args.treemap_size.as_deref() {
+        Some("loc") => TreemapSize::Loc,
+        _ => TreemapSize::Churn,
+    };
+    let treemap = Treemap {
+        size_by,
+        files: results
+            .iter()
+            .map(|r| TreemapFile {
+                file: r.file.clone(),
+                size: match size_by {
+                    TreemapSize::Churn => r.details.additions + r.details.deletions,
+                    TreemapSize::Loc => r.details.loc,
+                },
+                score: r.hotspot_score,
+            })
+            .collect(),
+    };
+
+    if args.all_files {
+        results.sort_by(|a, b| {
+            b.hotspot_score
+                .partial_cmp(&a.hotspot_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    } else if args.top == 0 {
         results.clear();
     } else {
         if results.len() > args.top {
@@ -483,12 +1097,59 @@ fn run_analysis(
         });
     }
 
+    for r in &mut results {
+        r.details.ownership = ownership_data.remove(&r.file);
+    }
+    // Blame only the top N rows, even with --all-files; it is by far the slowest
+    // git call. Rows beyond them leave line age, history and partners empty.
+    let detailed = results.len().min(args.top);
+    let shown: Vec<String> = results[..detailed].iter().map(|r| r.file.clone()).collect();
+    let line_ages = analyzers::code_age::median_line_ages(repo_path, &shown);
+    let mut histories = analyzers::history::file_histories(&commits, &shown);
+    for r in &mut results[..detailed] {
+        if let Some(age) = r.details.age.as_mut() {
+            age.median_line_age_days = line_ages.get(&r.file).copied();
+        }
+        r.details.history = histories.remove(&r.file);
+        r.details.coupled = analyzers::coupling::partners_of(&coupling_data, &r.file, |f| {
+            churn_data.get(f).map_or(0, |c| c.commit_count)
+        });
+    }
+
     let file_set: HashSet<&str> = filtered_files.iter().map(|s| s.as_str()).collect();
-    let top_couplings: Vec<CouplingEntry> = coupling_data
+    let mut top_couplings: Vec<CouplingEntry> = coupling_data
         .into_iter()
         .filter(|c| file_set.contains(c.file_a.as_str()) && file_set.contains(c.file_b.as_str()))
-        .take(10)
         .collect();
+    let coupling_clusters = analyzers::coupling::find_clusters(
+        &top_couplings,
+        args.cluster_strength
+            .unwrap_or(analyzers::coupling::DEFAULT_CLUSTER_STRENGTH),
+    );
+    let boundary_violations: Vec<CouplingEntry> = top_couplings
+        .iter()
+        .filter(|c| c.is_boundary_violation())
+        .cloned()
+        .collect();
+    let graph = graph_format.then(|| {
+        reporters::graph::build_graph(
+            &top_couplings,
+            &file_tiers,
+            &reporters::graph::GraphOptions {
+                min_strength: args
+                    .graph_min_strength
+                    .unwrap_or(reporters::graph::DEFAULT_MIN_STRENGTH),
+                max_nodes: args
+                    .graph_max_nodes
+                    .unwrap_or(reporters::graph::DEFAULT_MAX_NODES),
+                directories: args.graph_dirs,
+            },
+        )
+    });
+    // Tables export every coupled pair; other formats list the strongest.
+    if !matches!(args.format.as_str(), "csv" | "tsv" | "sqlite") {
+        top_couplings.truncate(10);
+    }
 
     let t5 = fmt_dur(step_start.elapsed());
     pb.println(format!(
@@ -498,7 +1159,7 @@ fn run_analysis(
 
     pb.finish_and_clear();
     eprintln!(
-        "✔ [{}] {} commits, {} files — ⏱ {}{}",
+        "✔ [{}] {} commits, {} files — ⏱ {}{}{}",
         repo_name,
         commits.len(),
         filtered_files.len(),
@@ -507,32 +1168,102 @@ fn run_analysis(
             String::new()
         } else {
             format!(" — ⚠ {} security risk(s)", security_risks.len())
+        },
+        if secret_findings.is_empty() {
+            String::new()
+        } else {
+            format!(" — 🔑 {} secret(s) in history", secret_findings.len())
         }
     );
 
-    let report = Report {
-        meta: ReportMeta {
-            since: if args.since.is_empty() {
-                "all history".to_string()
-            } else {
-                args.since.clone()
-            },
-            commit_count: commits.len(),
-            file_count: filtered_files.len(),
-            analyzed_at: chrono::Utc::now().to_rfc3339(),
-            repo_path: repo_path.display().to_string(),
+    let salt = args
+        .anonymize_salt
+        .as_deref()
+        .unwrap_or(anonymize::DEFAULT_SALT);
+    let meta = ReportMeta {
+        since: if args.since.is_empty() {
+            "all history".to_string()
+        } else {
+            args.since.clone()
         },
+        commit_count: commits.len(),
+        file_count: filtered_files.len(),
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+        repo_path: repo_path.display().to_string(),
+    };
+
+    if let Some(Command::Authors { teams: by_team, .. }) = &args.command {
+        let mut authors = analyzers::contributors::analyze_contributors(
+            &commits,
+            &filtered_files,
+            &results,
+            by_team.then_some(teams),
+        );
+        authors.truncate(args.top);
+        let mut report = AuthorReport {
+            meta,
+            group_by: if *by_team { "team" } else { "author" }.to_string(),
+            authors,
+        };
+        if args.anonymize {
+            anonymize::anonymize_author_report(&mut report, salt);
+        }
+        match args.format.as_str() {
+            "json" => reporters::json::report_json(&report, output_path)?,
+            "html" => {
+                let path = output_path.ok_or("output path required for html")?;
+                reporters::html::report_authors_html(&report, path)?;
+            }
+            _ => reporters::terminal::report_authors_terminal(&report),
+        }
+        return Ok(());
+    }
+
+    let mut report = Report {
+        meta,
+        weights: weights.clone(),
         results,
         couplings: top_couplings,
+        coupling_clusters,
+        boundary_violations,
         security_risks,
+        secret_findings,
+        security_suppressed: suppressed_risks + suppressed_secrets,
+        auto_excluded,
+        function_hotspots,
+        entropy_periods,
+        knowledge_risks,
+        treemap,
     };
+    if args.anonymize {
+        anonymize::anonymize_report(&mut report, salt);
+    }
 
-    match args.format.as_str() {
-        "json" => reporters::json::report_json(&report, output_path)?,
-        "html" => {
+    match (args.format.as_str(), &graph) {
+        ("json", _) => reporters::json::report_json(&report, output_path)?,
+        ("html", _) => {
             let path = output_path.ok_or("output path required for html")?;
             reporters::html::report_html(&report, path)?;
         }
+        ("csv", _) => reporters::csv::report_table(&report, ',', output_path)?,
+        ("tsv", _) => reporters::csv::report_table(&report, '\t', output_path)?,
+        #[cfg(feature = "sqlite")]
+        ("sqlite", _) => {
+            let path = output_path.ok_or("output path required for sqlite")?;
+            let mut commits = commits;
+            if args.anonymize {
+                anonymize::anonymize_commits(&mut commits, salt);
+            }
+            reporters::sqlite::report_sqlite(&report, repo_name, &commits, &commit_changes, path)?;
+        }
+        ("markdown", _) => reporters::markdown::report_markdown(
+            &report,
+            repo_name,
+            args.markdown_max_chars,
+            output_path,
+        )?,
+        ("svg", _) => reporters::treemap::report_treemap(&report.treemap, repo_name, output_path)?,
+        (format, Some(graph)) => reporters::graph::report_graph(graph, format, output_path)?,
         _ => {
             if is_multi {
                 // Print a visible repo header before the terminal report
@@ -659,6 +1390,24 @@ fn scan_for_repos(dir: &Path, depth: usize, repos: &mut Vec<PathBuf>) {
     }
 }
 
+/// Turns `file` into a repo-relative path with `/` separators. A path that
+/// exists from the current directory is resolved against the repository root;
+/// anything else is taken as already repo-relative.
+fn repo_relative(repo_path: &Path, file: &str) -> String {
+    let on_disk = std::fs::canonicalize(file).ok().and_then(|abs| {
+        let root = std::fs::canonicalize(repo_path).ok()?;
+        abs.strip_prefix(root).ok().map(Path::to_path_buf)
+    });
+    match on_disk {
+        Some(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => file.trim_start_matches("./").replace('\\', "/"),
+    }
+}
+
 // ── Output path helpers ────────────────────────────────────────────────────────
 
 /// Given a base output path and a repo name, insert the repo name before the extension.
@@ -1015,6 +1764,12 @@ mod tests {
         let coupling = analyzers::coupling::analyze_coupling(&commits, &files);
         let silo = analyzers::blame::analyze_authors(&commits, &files);
         let quality = analyzers::commit_quality::analyze_commit_quality(&commits, &files);
+        let mut measured = std::collections::HashMap::new();
+        git::tree::for_each_head_file(&repo, &files, |file, content| {
+            measured.insert(file.to_string(), analyzers::complexity::measure(content));
+        })
+        .expect("read HEAD files");
+        let complexity = analyzers::complexity::analyze_complexity(&measured, &files);
         let diff_stats = Default::default();
         let weights = Weights::default();
 
@@ -1027,6 +1782,13 @@ mod tests {
             &coupling,
             &silo,
             &quality,
+            &complexity,
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            &std::collections::HashMap::new(),
+            None,
             &diff_stats,
             &weights,
         );
@@ -1053,7 +1815,8 @@ mod tests {
                 "src/app.rs".to_string(),
             ],
         };
-        let risks = analyzers::security::analyze_security(&[commit]);
+        let (risks, _) =
+            analyzers::security::analyze_security(&[commit], &config::SecurityOverrides::default());
         let flagged: Vec<&str> = risks.iter().map(|r| r.file.as_str()).collect();
         assert!(flagged.contains(&".env"), ".env must be flagged");
         assert!(
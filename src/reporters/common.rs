@@ -0,0 +1,112 @@
+//! Helpers shared by more than one reporter.
+
+use crate::types::{HotspotResult, Tier};
+
+/// Critical hotspots below this line coverage (%) get a recommendation.
+const LOW_COVERAGE: f64 = 40.0;
+
+/// Refs listed per security risk; a file kept by every release tag would
+/// otherwise print one name per tag.
+const MAX_REFS_SHOWN: usize = 5;
+
+/// Escapes text for HTML and SVG, in element content and quoted attributes.
+pub(super) fn esc(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+/// Comma-separated `refs`, cut to the first [`MAX_REFS_SHOWN`] with
+/// "and N more". Empty when there are none.
+pub(super) fn ref_list(refs: &[String]) -> String {
+    let shown = refs[..refs.len().min(MAX_REFS_SHOWN)].join(", ");
+    match refs.len().saturating_sub(MAX_REFS_SHOWN) {
+        0 => shown,
+        more => format!("{shown} and {more} more"),
+    }
+}
+
+/// Actionable notes on the top results; `style(name, urgent)` formats each
+/// file name for the output format.
+pub(super) fn build_recommendations(
+    results: &[HotspotResult],
+    style: impl Fn(&str, bool) -> String,
+) -> Vec<String> {
+    let mut recs = Vec::new();
+    for r in results.iter().take(10) {
+        let name = r.file.split('/').next_back().unwrap_or(&r.file);
+        if let Some(cov) = r.line_coverage {
+            if r.tier == Tier::Critical && cov < LOW_COVERAGE {
+                recs.push(format!(
+                    "{} is a critical hotspot with {}% coverage — add tests before changing it further",
+                    style(name, true),
+                    cov.round()
+                ));
+            }
+        }
+        if r.details.top_author_percent >= 80.0 && r.details.author_count <= 2 {
+            recs.push(format!(
+                "{} has {}% single-author commits — consider a knowledge-transfer session",
+                style(name, false),
+                r.details.top_author_percent.round()
+            ));
+        }
+        if r.details.burst_incidents >= 3 {
+            recs.push(format!(
+                "{} shows burst patterns: {} rapid-commit windows detected",
+                style(name, false),
+                r.details.burst_incidents
+            ));
+        }
+        if r.details.revert_count >= 2 {
+            recs.push(format!(
+                "{} has been reverted {} times — consider adding tests or stricter review",
+                style(name, false),
+                r.details.revert_count
+            ));
+        }
+        if r.details.wip_commits >= 3 {
+            recs.push(format!(
+                "{} appears in {} WIP/low-quality commits — this area needs careful review",
+                style(name, false),
+                r.details.wip_commits
+            ));
+        }
+        if let Some(ratio) = r.details.tests.as_ref().and_then(|t| t.co_change_ratio) {
+            if r.details.commit_count >= 5 && ratio < 0.25 {
+                recs.push(format!(
+                    "{} changed with its tests in only {}% of {} commits — tests may be drifting",
+                    style(name, false),
+                    (ratio * 100.0).round(),
+                    r.details.commit_count
+                ));
+            }
+        }
+        if r.details.large_commit_count >= 3 {
+            recs.push(format!(
+                "{} was swept up in {} large commits — consider smaller, focused PRs",
+                style(name, false),
+                r.details.large_commit_count
+            ));
+        }
+        if recs.len() >= 8 {
+            break;
+        }
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ref_list_is_capped() {
+        let refs: Vec<String> = (1..=1500).map(|i| format!("v{i}")).collect();
+        assert_eq!(ref_list(&refs), "v1, v2, v3, v4, v5 and 1495 more");
+        assert_eq!(ref_list(&refs[..2]), "v1, v2");
+        assert_eq!(ref_list(&[]), "");
+    }
+}
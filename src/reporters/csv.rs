@@ -0,0 +1,493 @@
+use crate::types::{CouplingEntry, HotspotResult, Report, SecurityRisk};
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Every scalar of `HotspotResult` and `HotspotDetails`, nested structs
+/// flattened; lists are joined with `;`. Second item is the SQLite type.
+pub(super) const HOTSPOT_COLUMNS: &[(&str, &str)] = &[
+    ("rank", "INTEGER"),
+    ("file", "TEXT"),
+    ("hotspot_score", "REAL"),
+    ("tier", "TEXT"),
+    ("quadrant", "TEXT"),
+    ("churn_score", "REAL"),
+    ("bug_fix_score", "REAL"),
+    ("revert_score", "REAL"),
+    ("burst_score", "REAL"),
+    ("coupling_score", "REAL"),
+    ("silo_score", "REAL"),
+    ("commit_quality_score", "REAL"),
+    ("complexity_score", "REAL"),
+    ("test_gap_score", "REAL"),
+    ("line_coverage", "REAL"),
+    ("coverage_gap_score", "REAL"),
+    ("entropy_score", "REAL"),
+    ("experience_score", "REAL"),
+    ("stability_dampened", "INTEGER"),
+    ("commit_count", "INTEGER"),
+    ("bug_commits", "INTEGER"),
+    ("revert_count", "INTEGER"),
+    ("burst_incidents", "INTEGER"),
+    ("wip_commits", "INTEGER"),
+    ("large_commit_count", "INTEGER"),
+    ("top_author", "TEXT"),
+    ("top_author_percent", "REAL"),
+    ("author_count", "INTEGER"),
+    ("additions", "INTEGER"),
+    ("deletions", "INTEGER"),
+    ("loc", "INTEGER"),
+    ("max_indent", "INTEGER"),
+    ("longest_function", "INTEGER"),
+    ("test_files", "TEXT"),
+    ("inline_tests", "INTEGER"),
+    ("test_co_changed_commits", "INTEGER"),
+    ("test_co_change_ratio", "REAL"),
+    ("last_modified_days", "INTEGER"),
+    ("first_seen_days", "INTEGER"),
+    ("median_line_age_days", "INTEGER"),
+    ("age_class", "TEXT"),
+    ("low_experience_changes", "INTEGER"),
+    ("avg_prior_file_commits", "REAL"),
+    ("avg_prior_repo_commits", "REAL"),
+    ("primary_authors", "TEXT"),
+    ("primary_last_active_days", "INTEGER"),
+    ("orphaned", "INTEGER"),
+    ("authors", "TEXT"),
+    ("coupled_files", "TEXT"),
+];
+
+pub(super) const COUPLING_COLUMNS: &[(&str, &str)] = &[
+    ("file_a", "TEXT"),
+    ("file_b", "TEXT"),
+    ("co_changes", "INTEGER"),
+    ("strength", "REAL"),
+    ("boundary", "TEXT"),
+    ("boundary_allowed", "INTEGER"),
+];
+
+pub(super) const SECURITY_COLUMNS: &[(&str, &str)] = &[
+    ("file", "TEXT"),
+    ("risk_type", "TEXT"),
+    ("severity", "TEXT"),
+    ("commit_count", "INTEGER"),
+    ("first_seen", "TEXT"),
+    ("last_seen", "TEXT"),
+    ("present_at_head", "INTEGER"),
+    ("refs", "TEXT"),
+    ("added_in", "TEXT"),
+    ("removed_in", "TEXT"),
+    ("authors", "TEXT"),
+];
+
+/// A cell; CSV renders it as text, SQLite binds it with its own type.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Value {
+    Null,
+    Int(i64),
+    Real(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Value::Int(v as i64)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Int(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Real(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Text(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+/// Writes one row per result as CSV (`delimiter == ','`) or TSV. When
+/// `output` is a directory, `couplings` and `security_risks` tables are
+/// written next to `hotspots`; otherwise the hotspot table goes to the file,
+/// or stdout.
+pub fn report_table(report: &Report, delimiter: char, output: Option<&Path>) -> Result<(), String> {
+    let ext = if delimiter == '\t' { "tsv" } else { "csv" };
+    let hotspots = table(
+        HOTSPOT_COLUMNS,
+        report
+            .results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| hotspot_row(i + 1, r)),
+        delimiter,
+    );
+
+    match output {
+        Some(dir) if is_dir_output(dir) => {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+            let tables = [
+                ("hotspots", hotspots),
+                (
+                    "couplings",
+                    table(
+                        COUPLING_COLUMNS,
+                        report.couplings.iter().map(coupling_row),
+                        delimiter,
+                    ),
+                ),
+                (
+                    "security_risks",
+                    table(
+                        SECURITY_COLUMNS,
+                        report.security_risks.iter().map(security_row),
+                        delimiter,
+                    ),
+                ),
+            ];
+            for (name, text) in tables {
+                let path = dir.join(format!("{name}.{ext}"));
+                fs::write(&path, text)
+                    .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            }
+            eprintln!(
+                "✓ {} tables written to {} (hotspots, couplings, security_risks)",
+                ext.to_uppercase(),
+                dir.display()
+            );
+        }
+        Some(path) => {
+            fs::write(path, &hotspots)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            eprintln!("✓ {} written to {}", ext.to_uppercase(), path.display());
+        }
+        None => std::io::stdout()
+            .lock()
+            .write_all(hotspots.as_bytes())
+            .map_err(|e| format!("Failed to write stdout: {e}"))?,
+    }
+    Ok(())
+}
+
+/// An existing directory, or a path ending in a separator.
+pub fn is_dir_output(path: &Path) -> bool {
+    path.is_dir() || path.as_os_str().to_string_lossy().ends_with(['/', '\\'])
+}
+
+pub(super) fn hotspot_row(rank: usize, r: &HotspotResult) -> Vec<Value> {
+    let d = &r.details;
+    let tests = d.tests.as_ref();
+    let age = d.age.as_ref();
+    let exp = d.experience.as_ref();
+    let own = d.ownership.as_ref();
+    vec![
+        rank.into(),
+        r.file.as_str().into(),
+        r.hotspot_score.into(),
+        label(&r.tier).into(),
+        label(&r.quadrant).into(),
+        r.churn_score.into(),
+        r.bug_fix_score.into(),
+        r.revert_score.into(),
+        r.burst_score.into(),
+        r.coupling_score.into(),
+        r.silo_score.into(),
+        r.commit_quality_score.into(),
+        r.complexity_score.into(),
+        r.test_gap_score.into(),
+        r.line_coverage.into(),
+        r.coverage_gap_score.into(),
+        r.entropy_score.into(),
+        r.experience_score.into(),
+        r.stability_dampened.into(),
+        d.commit_count.into(),
+        d.bug_commits.into(),
+        d.revert_count.into(),
+        d.burst_incidents.into(),
+        d.wip_commits.into(),
+        d.large_commit_count.into(),
+        d.top_author.as_str().into(),
+        d.top_author_percent.into(),
+        d.author_count.into(),
+        d.additions.into(),
+        d.deletions.into(),
+        d.loc.into(),
+        d.max_indent.into(),
+        d.longest_function.into(),
+        tests.map(|t| t.test_files.join(";")).into(),
+        tests.map(|t| t.inline_tests).into(),
+        tests.map(|t| t.co_changed_commits).into(),
+        tests.and_then(|t| t.co_change_ratio).into(),
+        age.map(|a| a.last_modified_days).into(),
+        age.map(|a| a.first_seen_days).into(),
+        age.and_then(|a| a.median_line_age_days).into(),
+        age.map(|a| label(&a.age_class)).into(),
+        exp.map(|e| e.low_experience_changes).into(),
+        exp.map(|e| e.avg_prior_file_commits).into(),
+        exp.map(|e| e.avg_prior_repo_commits).into(),
+        own.map(|o| o.primary_authors.join(";")).into(),
+        own.map(|o| o.primary_last_active_days).into(),
+        own.map(|o| o.orphaned).into(),
+        d.history
+            .as_ref()
+            .map(|h| {
+                h.authors
+                    .iter()
+                    .map(|a| format!("{}:{}", a.author, a.commits))
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .into(),
+        d.coupled
+            .iter()
+            .map(|c| c.file.as_str())
+            .collect::<Vec<_>>()
+            .join(";")
+            .into(),
+    ]
+}
+
+pub(super) fn coupling_row(c: &CouplingEntry) -> Vec<Value> {
+    vec![
+        c.file_a.as_str().into(),
+        c.file_b.as_str().into(),
+        c.co_changes.into(),
+        c.strength.into(),
+        c.boundary.as_deref().into(),
+        c.boundary_allowed.into(),
+    ]
+}
+
+pub(super) fn security_row(r: &SecurityRisk) -> Vec<Value> {
+    vec![
+        r.file.as_str().into(),
+        r.risk_type.as_str().into(),
+        r.severity.as_str().into(),
+        r.commit_count.into(),
+        r.first_seen.as_str().into(),
+        r.last_seen.as_str().into(),
+        r.present_at_head.into(),
+        r.refs.join(";").into(),
+        r.added_in.join(";").into(),
+        r.removed_in.join(";").into(),
+        r.authors.join(";").into(),
+    ]
+}
+
+fn table(
+    columns: &[(&str, &str)],
+    rows: impl Iterator<Item = Vec<Value>>,
+    delimiter: char,
+) -> String {
+    let sep = delimiter.to_string();
+    let mut out = columns
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(&sep);
+    out.push('\n');
+    for row in rows {
+        debug_assert_eq!(row.len(), columns.len(), "row does not match the header");
+        let line: Vec<String> = row.iter().map(|v| field(v, delimiter)).collect();
+        out.push_str(&line.join(&sep));
+        out.push('\n');
+    }
+    out
+}
+
+/// RFC 4180 quoting for CSV; TSV has no quoting, so separators become spaces.
+/// Text that a spreadsheet would run as a formula gets a leading `'`: paths
+/// and author names come from the scanned repository.
+fn field(value: &Value, delimiter: char) -> String {
+    let text = match value {
+        Value::Null => return String::new(),
+        Value::Int(v) => return v.to_string(),
+        Value::Real(v) => return num(*v),
+        Value::Bool(v) => return v.to_string(),
+        Value::Text(s) if s.starts_with(['=', '+', '-', '@', '\t', '\r']) => &format!("'{s}"),
+        Value::Text(s) => s,
+    };
+    if delimiter == '\t' {
+        text.replace(['\t', '\n', '\r'], " ")
+    } else if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.clone()
+    }
+}
+
+/// Two decimals, trailing zeros dropped.
+fn num(v: f64) -> String {
+    let s = format!("{v:.2}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// An enum's name as it appears in JSON.
+fn label<T: Serialize>(v: &T) -> String {
+    serde_json::to_value(v)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CoupledFile, HotspotDetails, Quadrant, Tier};
+
+    #[test]
+    fn test_fields_quoted_per_format() {
+        let cols = [("x", "TEXT"), ("y", "TEXT")];
+        let rows = vec![vec![Value::from("a,b"), Value::from("say \"hi\"")]];
+        assert_eq!(
+            table(&cols, rows.into_iter(), ','),
+            "x,y\n\"a,b\",\"say \"\"hi\"\"\"\n"
+        );
+        let tabbed = vec![vec![Value::from("a\tb"), Value::from("c\nd")]];
+        assert_eq!(table(&cols, tabbed.into_iter(), '\t'), "x\ty\na b\tc d\n");
+        let typed = vec![vec![Value::from(Some(33.3333)), Value::from(None::<f64>)]];
+        assert_eq!(table(&cols, typed.into_iter(), ','), "x,y\n33.33,\n");
+        let formulas = vec![vec![Value::from("=HYPERLINK(\"x\")"), Value::from("@sum")]];
+        assert_eq!(
+            table(&cols, formulas.into_iter(), ','),
+            "x,y\n\"'=HYPERLINK(\"\"x\"\")\",'@sum\n"
+        );
+        let signed = vec![vec![Value::from("+1 ann"), Value::from(-3_i64)]];
+        assert_eq!(
+            table(&cols, signed.into_iter(), '\t'),
+            "x\ty\n'+1 ann\t-3\n"
+        );
+        assert_eq!(num(42.0), "42");
+        assert_eq!(num(0.5), "0.5");
+    }
+
+    #[test]
+    fn test_hotspot_row_matches_headers() {
+        let r = HotspotResult {
+            file: "src/app.rs".to_string(),
+            hotspot_score: 71.5,
+            churn_score: 80.0,
+            bug_fix_score: 60.0,
+            revert_score: 0.0,
+            burst_score: 10.0,
+            coupling_score: 20.0,
+            silo_score: 50.0,
+            commit_quality_score: 5.0,
+            complexity_score: 40.0,
+            test_gap_score: 0.0,
+            line_coverage: None,
+            coverage_gap_score: 0.0,
+            entropy_score: 0.0,
+            experience_score: 0.0,
+            stability_dampened: false,
+            quadrant: Quadrant::Hotspot,
+            tier: Tier::High,
+            details: HotspotDetails {
+                commit_count: 12,
+                bug_commits: 4,
+                revert_count: 0,
+                burst_incidents: 1,
+                wip_commits: 0,
+                large_commit_count: 2,
+                top_author: "ann".to_string(),
+                top_author_percent: 75.0,
+                author_count: 2,
+                additions: 300,
+                deletions: 120,
+                loc: 250,
+                max_indent: 4,
+                longest_function: 60,
+                tests: None,
+                age: None,
+                experience: None,
+                ownership: None,
+                history: None,
+                coupled: vec![CoupledFile {
+                    file: "src/lib.rs".to_string(),
+                    support: 3,
+                    confidence: 25.0,
+                    reverse_confidence: 50.0,
+                    strength: 20.0,
+                }],
+            },
+        };
+        let row = hotspot_row(3, &r);
+        assert_eq!(row.len(), HOTSPOT_COLUMNS.len());
+        let cell = |name: &str| {
+            let i = HOTSPOT_COLUMNS
+                .iter()
+                .position(|(n, _)| *n == name)
+                .unwrap();
+            row[i].clone()
+        };
+        assert_eq!(cell("rank"), Value::Int(3));
+        assert_eq!(cell("file"), Value::from("src/app.rs"));
+        assert_eq!(cell("hotspot_score"), Value::Real(71.5));
+        assert_eq!(cell("tier"), Value::from("High"));
+        assert_eq!(cell("quadrant"), Value::from("hotspot"));
+        assert_eq!(cell("bug_commits"), Value::Int(4));
+        assert_eq!(cell("top_author"), Value::from("ann"));
+        assert_eq!(cell("loc"), Value::Int(250));
+        assert_eq!(cell("line_coverage"), Value::Null);
+        assert_eq!(cell("median_line_age_days"), Value::Null);
+        assert_eq!(cell("coupled_files"), Value::from("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_coupling_and_security_rows_match_headers() {
+        let c = CouplingEntry {
+            file_a: "a.rs".to_string(),
+            file_b: "b.rs".to_string(),
+            co_changes: 3,
+            strength: 60.0,
+            boundary: Some("api ↔ web".to_string()),
+            boundary_allowed: false,
+        };
+        assert_eq!(coupling_row(&c).len(), COUPLING_COLUMNS.len());
+        let r = SecurityRisk {
+            file: ".env".to_string(),
+            risk_type: "env-file".to_string(),
+            severity: "high".to_string(),
+            commit_count: 2,
+            first_seen: "2024-01-01".to_string(),
+            last_seen: "2024-02-01".to_string(),
+            present_at_head: false,
+            refs: vec![],
+            added_in: vec!["abc".to_string(), "def".to_string()],
+            removed_in: vec![],
+            authors: vec!["ann".to_string()],
+        };
+        let row = security_row(&r);
+        assert_eq!(row.len(), SECURITY_COLUMNS.len());
+        assert_eq!(row[8], Value::from("abc;def"));
+        assert!(is_dir_output(Path::new("out/")));
+        assert!(!is_dir_output(Path::new("out/hotspots.csv")));
+    }
+}
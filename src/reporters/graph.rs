@@ -0,0 +1,299 @@
+use crate::types::{CouplingEntry, Tier};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Default minimum coupling strength (%) for an edge.
+pub const DEFAULT_MIN_STRENGTH: f64 = 20.0;
+
+/// Default cap on nodes; the strongest edges are kept first.
+pub const DEFAULT_MAX_NODES: usize = 40;
+
+/// Thresholds for `--format dot` and `--format mermaid`.
+#[derive(Debug, Clone)]
+pub struct GraphOptions {
+    pub min_strength: f64,
+    pub max_nodes: usize,
+    /// One node per parent directory instead of per file.
+    pub directories: bool,
+}
+
+/// A file or directory; `None` tier when it was not scored.
+struct Node {
+    name: String,
+    tier: Option<Tier>,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    strength: f64,
+    co_changes: usize,
+}
+
+/// Coupling graph trimmed to the configured thresholds.
+pub struct CouplingGraph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+/// Keeps edges at or above `min_strength`, strongest first, while they fit in
+/// `max_nodes`. In directory mode, file pairs are merged per directory pair
+/// (strongest link, summed co-changes) and each node takes its worst tier.
+pub fn build_graph(
+    couplings: &[CouplingEntry],
+    tiers: &HashMap<String, Tier>,
+    opts: &GraphOptions,
+) -> CouplingGraph {
+    let node_of = |file: &str| -> String {
+        if opts.directories {
+            file.rsplit_once('/')
+                .map_or(".", |(dir, _)| dir)
+                .to_string()
+        } else {
+            file.to_string()
+        }
+    };
+
+    let mut merged: BTreeMap<(String, String), (f64, usize)> = BTreeMap::new();
+    for c in couplings {
+        let (a, b) = (node_of(&c.file_a), node_of(&c.file_b));
+        if a == b {
+            continue;
+        }
+        let key = if a < b { (a, b) } else { (b, a) };
+        let entry = merged.entry(key).or_insert((0.0, 0));
+        entry.0 = entry.0.max(c.strength);
+        entry.1 += c.co_changes;
+    }
+    let mut candidates: Vec<((String, String), (f64, usize))> = merged
+        .into_iter()
+        .filter(|(_, (strength, _))| *strength >= opts.min_strength)
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.1 .0
+            .partial_cmp(&a.1 .0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.1 .1.cmp(&a.1 .1))
+    });
+
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut edges = Vec::new();
+    for ((a, b), (strength, co_changes)) in candidates {
+        let new = [&a, &b].iter().filter(|n| !index.contains_key(**n)).count();
+        if nodes.len() + new > opts.max_nodes {
+            continue;
+        }
+        let mut id = |name: String| {
+            *index.entry(name.clone()).or_insert_with(|| {
+                nodes.push(Node { name, tier: None });
+                nodes.len() - 1
+            })
+        };
+        let (from, to) = (id(a), id(b));
+        edges.push(Edge {
+            from,
+            to,
+            strength,
+            co_changes,
+        });
+    }
+
+    for (file, tier) in tiers {
+        if let Some(&i) = index.get(&node_of(file)) {
+            let node = &mut nodes[i];
+            if node.tier.as_ref().is_none_or(|t| rank(tier) < rank(t)) {
+                node.tier = Some(tier.clone());
+            }
+        }
+    }
+    CouplingGraph { nodes, edges }
+}
+
+/// Writes the graph as Graphviz DOT (`format == "dot"`) or a Mermaid
+/// flowchart, to a file if given, otherwise stdout.
+pub fn report_graph(
+    graph: &CouplingGraph,
+    format: &str,
+    output_file: Option<&Path>,
+) -> Result<(), String> {
+    let (text, label) = if format == "dot" {
+        (render_dot(graph), "DOT graph")
+    } else {
+        (render_mermaid(graph), "Mermaid graph")
+    };
+    if let Some(path) = output_file {
+        fs::write(path, &text).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        eprintln!("✓ {label} written to {}", path.display());
+    } else {
+        std::io::stdout()
+            .lock()
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write stdout: {e}"))?;
+    }
+    Ok(())
+}
+
+fn render_dot(graph: &CouplingGraph) -> String {
+    let mut out = String::from(
+        "graph coupling {\n  graph [overlap=false, splines=true];\n  \
+         node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\", fontsize=10];\n  \
+         edge [color=\"#64748b\", fontname=\"Helvetica\", fontsize=9];\n",
+    );
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  \"{}\" [fillcolor=\"{}\"];\n",
+            dot_escape(&node.name),
+            fill(node.tier.as_ref())
+        ));
+    }
+    for e in &graph.edges {
+        out.push_str(&format!(
+            "  \"{}\" -- \"{}\" [penwidth={:.1}, label=\"{}% ({}x)\"];\n",
+            dot_escape(&graph.nodes[e.from].name),
+            dot_escape(&graph.nodes[e.to].name),
+            pen_width(e.strength),
+            e.strength.round(),
+            e.co_changes
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(graph: &CouplingGraph) -> String {
+    let mut out = String::from("graph LR\n");
+    for (i, node) in graph.nodes.iter().enumerate() {
+        out.push_str(&format!(
+            "  n{i}[\"{}\"]:::{}\n",
+            node.name.replace('"', "#quot;"),
+            node.tier.as_ref().map_or("unscored", |t| tier_class(t))
+        ));
+    }
+    for e in &graph.edges {
+        out.push_str(&format!(
+            "  n{} ---|\"{}% ({}x)\"| n{}\n",
+            e.from,
+            e.strength.round(),
+            e.co_changes,
+            e.to
+        ));
+    }
+    for (i, e) in graph.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "  linkStyle {i} stroke-width:{:.1}px\n",
+            pen_width(e.strength)
+        ));
+    }
+    for tier in [Tier::Critical, Tier::High, Tier::Medium, Tier::Low] {
+        out.push_str(&format!(
+            "  classDef {} fill:{},stroke:#334155\n",
+            tier_class(&tier),
+            fill(Some(&tier))
+        ));
+    }
+    out.push_str(&format!(
+        "  classDef unscored fill:{},stroke:#334155\n",
+        fill(None)
+    ));
+    out
+}
+
+/// Lower is worse, for picking a directory's tier.
+fn rank(tier: &Tier) -> u8 {
+    match tier {
+        Tier::Critical => 0,
+        Tier::High => 1,
+        Tier::Medium => 2,
+        Tier::Low => 3,
+    }
+}
+
+fn tier_class(tier: &Tier) -> &'static str {
+    match tier {
+        Tier::Critical => "critical",
+        Tier::High => "high",
+        Tier::Medium => "medium",
+        Tier::Low => "low",
+    }
+}
+
+/// Same palette as the HTML tier badges.
+fn fill(tier: Option<&Tier>) -> &'static str {
+    match tier {
+        Some(Tier::Critical) => "#fca5a5",
+        Some(Tier::High) => "#fdba74",
+        Some(Tier::Medium) => "#fde047",
+        Some(Tier::Low) => "#86efac",
+        None => "#e2e8f0",
+    }
+}
+
+/// 1px at 0% strength up to 5px at 100%.
+fn pen_width(strength: f64) -> f64 {
+    1.0 + strength.clamp(0.0, 100.0) / 25.0
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(a: &str, b: &str, strength: f64) -> CouplingEntry {
+        CouplingEntry {
+            file_a: a.to_string(),
+            file_b: b.to_string(),
+            co_changes: 4,
+            strength,
+            boundary: None,
+            boundary_allowed: false,
+        }
+    }
+
+    #[test]
+    fn test_graph_thresholds_and_directory_mode() {
+        let couplings = vec![
+            entry("src/a.rs", "src/b.rs", 80.0),
+            entry("src/a.rs", "web/c.ts", 50.0),
+            entry("src/b.rs", "web/d.ts", 60.0),
+            entry("docs/x.md", "docs/y.md", 10.0),
+        ];
+        let tiers = HashMap::from([
+            ("src/a.rs".to_string(), Tier::Low),
+            ("src/b.rs".to_string(), Tier::Critical),
+        ]);
+        let opts = GraphOptions {
+            min_strength: 20.0,
+            max_nodes: 3,
+            directories: false,
+        };
+        let graph = build_graph(&couplings, &tiers, &opts);
+        let names: Vec<&str> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["src/a.rs", "src/b.rs", "web/d.ts"],
+            "Strongest edges first; a.rs ↔ c.ts does not fit"
+        );
+        assert_eq!(graph.edges.len(), 2);
+        assert!(render_dot(&graph).contains("\"src/a.rs\" -- \"src/b.rs\""));
+        assert!(render_mermaid(&graph).contains("n0[\"src/a.rs\"]:::low"));
+
+        let dirs = build_graph(
+            &couplings,
+            &tiers,
+            &GraphOptions {
+                directories: true,
+                ..opts
+            },
+        );
+        assert_eq!(dirs.nodes.len(), 2, "Same-directory pairs are dropped");
+        assert_eq!(dirs.edges[0].strength, 60.0);
+        assert_eq!(dirs.edges[0].co_changes, 8);
+        assert_eq!(dirs.nodes[0].tier, Some(Tier::Critical), "Worst tier wins");
+    }
+}
@@ -1,13 +1,172 @@
-use crate::types::{CouplingEntry, HotspotResult, Report, ReportMeta, SecurityRisk, Tier};
+use super::common::{esc, ref_list};
+use crate::types::{
+    AuthorReport, AuthorRisk, CouplingCluster, CouplingEntry, CouplingQuery, FunctionHotspot,
+    GeneratedFile, HotspotResult, Quadrant, Report, ReportMeta, SecretFinding, SecurityRisk,
+    TestCoevolution, Tier, Treemap, Weights,
+};
 use std::fs;
 use std::path::Path;
 
+/// Page styles shared by the hotspot and authors reports.
+const STYLE: &str = "\
+*,*::before,*::after{box-sizing:border-box;margin:0;padding:0}
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;background:#0f172a;color:#e2e8f0;padding:2rem;font-size:14px;line-height:1.6}
+h1{font-size:1.75rem;font-weight:800} h1 .fire{color:#f97316}
+h2{font-size:1rem;font-weight:700;color:#94a3b8;text-transform:uppercase;letter-spacing:.06em;margin-bottom:1rem}
+.meta{color:#64748b;margin:.4rem 0 2rem;font-size:.8rem} .meta span{margin-right:1.5rem}
+.stats{display:grid;grid-template-columns:repeat(auto-fit,minmax(160px,1fr));gap:1rem;margin-bottom:1.5rem}
+.stat{background:#1e293b;border:1px solid #334155;border-radius:.625rem;padding:1rem 1.25rem}
+.stat-label{font-size:.7rem;text-transform:uppercase;letter-spacing:.06em;color:#64748b}
+.stat-value{font-size:2rem;font-weight:800;margin-top:.2rem}
+.stat-value.red{color:#f87171} .stat-value.orange{color:#fb923c} .stat-value.blue{color:#60a5fa} .stat-value.slate{color:#94a3b8}
+.card{background:#1e293b;border:1px solid #334155;border-radius:.75rem;padding:1.5rem;margin-bottom:1.5rem}
+.security-card{border-color:#ef4444}
+.security-note{color:#fca5a5;font-size:.8rem;margin-bottom:1rem}
+.security-note code{background:#1a0e0e;padding:.1rem .3rem;border-radius:.2rem;font-size:.75rem}
+.chart-wrap svg{display:block;width:100%;height:auto}
+.chart-wrap text{fill:#64748b;font-size:11px;font-family:inherit}
+.treemap-wrap svg{display:block;width:100%;height:auto}
+table{width:100%;border-collapse:collapse}
+th{text-align:left;padding:.5rem .75rem;border-bottom:2px solid #334155;font-size:.7rem;font-weight:700;text-transform:uppercase;letter-spacing:.06em;color:#64748b}
+td{padding:.5rem .75rem;border-bottom:1px solid #1a2744;vertical-align:middle}
+tr:last-child td{border-bottom:none} tr:hover td{background:#162032}
+td.path{font-family:'JetBrains Mono','Fira Code',ui-monospace,monospace;font-size:.78rem;color:#7dd3fc;word-break:break-all}
+td.num{text-align:right;color:#94a3b8}
+.dim{color:#475569;font-size:.8em} .warn{color:#fbbf24;font-weight:600}
+.badge{display:inline-block;padding:.15rem .5rem;border-radius:999px;font-size:.7rem;font-weight:700;white-space:nowrap}
+.badge-critical{background:rgba(239,68,68,.15);color:#fca5a5}
+.badge-high{background:rgba(249,115,22,.15);color:#fdba74}
+.badge-medium{background:rgba(234,179,8,.15);color:#fde047}
+.badge-low{background:rgba(34,197,94,.15);color:#86efac}
+.footer{text-align:center;color:#334155;font-size:.75rem;margin-top:2rem}
+table.sortable th{cursor:pointer;user-select:none}
+th[data-dir=asc]::after{content:' ▲'} th[data-dir=desc]::after{content:' ▼'}
+#hotspots tbody tr{cursor:pointer}
+.filters{display:flex;gap:.75rem;margin-bottom:1rem}
+.filters input,.filters select{background:#0f172a;border:1px solid #334155;color:#e2e8f0;border-radius:.375rem;padding:.4rem .6rem;font:inherit}
+.filters input{flex:1;max-width:360px}
+#detail{position:fixed;top:0;right:0;bottom:0;width:min(600px,100%);overflow-y:auto;background:#111827;border-left:1px solid #334155;padding:1.5rem;box-shadow:-8px 0 24px rgba(0,0,0,.45);z-index:10}
+#detail h2{text-transform:none;letter-spacing:0;color:#7dd3fc;font-family:'JetBrains Mono','Fira Code',ui-monospace,monospace;word-break:break-all;padding-right:2rem}
+#detail h3{font-size:.75rem;text-transform:uppercase;letter-spacing:.06em;color:#94a3b8;margin:1.5rem 0 .5rem}
+#detail .close{position:absolute;top:.75rem;right:1rem;background:none;border:none;color:#94a3b8;font-size:1.5rem;cursor:pointer}
+.bar{display:inline-block;width:80px;height:6px;background:#334155;border-radius:3px;margin-right:.5rem;vertical-align:middle}
+.bar>span{display:block;height:100%;background:#f97316;border-radius:3px}
+";
+
+/// Sortable tables, hotspot filters and the per-file drill-down panel. Reads
+/// the results embedded as JSON in `#scanline-data`; builds DOM nodes with
+/// `textContent` only, so no data is ever parsed as HTML.
+const SCRIPT: &str = r#"(() => {
+  const data = JSON.parse(document.getElementById('scanline-data').textContent);
+  const SIGNALS = [
+    ['Churn', 'churn_score', 'churn'], ['Bug fixes', 'bug_fix_score', 'bugs'],
+    ['Reverts', 'revert_score', 'reverts'], ['Bursts', 'burst_score', 'bursts'],
+    ['Coupling', 'coupling_score', 'coupling'], ['Knowledge silo', 'silo_score', 'silo'],
+    ['Commit quality', 'commit_quality_score', 'commit_quality'], ['Complexity', 'complexity_score', 'complexity'],
+    ['Test gap', 'test_gap_score', 'tests'], ['Coverage gap', 'coverage_gap_score', 'coverage'],
+    ['Entropy', 'entropy_score', 'entropy'], ['Experience', 'experience_score', 'experience'],
+  ];
+  const el = (tag, props, ...kids) => {
+    const e = Object.assign(document.createElement(tag), props || {});
+    e.append(...kids);
+    return e;
+  };
+  const pct = v => Math.round(v) + '%';
+  const bar = v => {
+    const fill = el('span');
+    fill.style.width = Math.max(0, Math.min(100, v)) + '%';
+    return el('span', {}, el('span', { className: 'bar' }, fill), pct(v));
+  };
+  const table = (headers, rows) => el('table', {},
+    el('thead', {}, el('tr', {}, ...headers.map(h => el('th', { textContent: h })))),
+    el('tbody', {}, ...rows.map(cells => el('tr', {}, ...cells.map(c =>
+      c instanceof Node ? el('td', {}, c) : el('td', { textContent: c }))))));
+
+  document.querySelectorAll('table.sortable').forEach(t => {
+    t.querySelectorAll('thead th').forEach((th, col) => th.addEventListener('click', () => {
+      const asc = th.dataset.dir !== 'asc';
+      t.querySelectorAll('thead th').forEach(h => delete h.dataset.dir);
+      th.dataset.dir = asc ? 'asc' : 'desc';
+      const key = row => {
+        const cell = row.cells[col];
+        const v = cell.dataset.v ?? cell.textContent.trim();
+        const n = parseFloat(v);
+        return isNaN(n) ? v.toLowerCase() : n;
+      };
+      const rows = [...t.tBodies[0].rows].sort((a, b) => {
+        const x = key(a), y = key(b);
+        const c = typeof x === typeof y ? (x < y ? -1 : x > y ? 1 : 0) : (typeof x === 'number' ? -1 : 1);
+        return asc ? c : -c;
+      });
+      t.tBodies[0].append(...rows);
+    }));
+  });
+
+  const rows = document.querySelectorAll('#hotspots tbody tr');
+  const filter = document.getElementById('filter');
+  const tier = document.getElementById('tier-filter');
+  const applyFilter = () => {
+    const q = filter.value.trim().toLowerCase();
+    rows.forEach(row => {
+      const r = data.results[row.dataset.i];
+      row.hidden = !r.file.toLowerCase().includes(q) || (tier.value !== '' && r.tier !== tier.value);
+    });
+  };
+  filter.addEventListener('input', applyFilter);
+  tier.addEventListener('change', applyFilter);
+
+  const panel = document.getElementById('detail');
+  const show = i => {
+    const r = data.results[i], d = r.details, w = data.weights;
+    const damp = r.stability_dampened ? 0.5 : 1;
+    panel.replaceChildren(
+      el('button', { className: 'close', textContent: '×', title: 'Close (Esc)', onclick: () => { panel.hidden = true; } }),
+      el('h2', { textContent: r.file }),
+      el('p', { className: 'meta', textContent: `Score ${Math.round(r.hotspot_score)} · ${r.tier} · ${d.commit_count} commits`
+        + (r.stability_dampened ? ' · halved: untouched for the stable period' : '') }),
+      el('h3', { textContent: 'Score breakdown' }),
+      table(['Signal', 'Score', 'Weight', 'Points'], SIGNALS.filter(s => w[s[2]] > 0).map(([label, field, key]) =>
+        [label, bar(r[field]), pct(w[key] * 100), (r[field] * w[key] * damp).toFixed(1)])));
+    if (d.history) {
+      panel.append(el('h3', { textContent: `Authors (${d.history.authors.length})` }),
+        table(['Author', 'Commits', 'Share'], d.history.authors.map(a => [a.author, String(a.commits), bar(a.percent)])));
+    }
+    if (d.coupled.length) {
+      panel.append(el('h3', { textContent: 'Changes together with' }),
+        table(['File', 'Confidence', 'Support', 'Strength'], d.coupled.map(c =>
+          [c.file, bar(c.confidence), String(c.support), pct(c.strength)])));
+    }
+    if (d.history) {
+      const shown = d.history.commits.length;
+      panel.append(el('h3', { textContent: shown < d.commit_count ? `Latest ${shown} of ${d.commit_count} commits` : `Commits (${shown})` }),
+        table(['Date', 'Commit', 'Author', 'Subject'], d.history.commits.map(c =>
+          [c.date, c.hash.slice(0, 8), c.author, (c.bug_fix ? '🐛 ' : '') + (c.revert ? '↩ ' : '') + c.subject])));
+    }
+    panel.hidden = false;
+    panel.scrollTop = 0;
+  };
+  rows.forEach(row => {
+    row.addEventListener('click', () => show(row.dataset.i));
+    row.addEventListener('keydown', e => { if (e.key === 'Enter') show(row.dataset.i); });
+  });
+  document.addEventListener('keydown', e => { if (e.key === 'Escape') panel.hidden = true; });
+})();"#;
+
 pub fn report_html(report: &Report, output_file: &Path) -> Result<(), String> {
     let html = build_html(
         &report.meta,
+        &report.weights,
         &report.results,
         &report.couplings,
+        &report.coupling_clusters,
+        &report.boundary_violations,
         &report.security_risks,
+        &report.secret_findings,
+        report.security_suppressed,
+        &report.auto_excluded,
+        &report.function_hotspots,
+        &report.knowledge_risks,
+        &report.treemap,
     );
     fs::write(output_file, &html)
         .map_err(|e| format!("Failed to write {}: {e}", output_file.display()))?;
@@ -15,42 +174,39 @@ pub fn report_html(report: &Report, output_file: &Path) -> Result<(), String> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_html(
     meta: &ReportMeta,
+    weights: &Weights,
     results: &[HotspotResult],
     couplings: &[CouplingEntry],
+    clusters: &[CouplingCluster],
+    boundary_violations: &[CouplingEntry],
     security_risks: &[SecurityRisk],
+    secret_findings: &[SecretFinding],
+    suppressed: usize,
+    auto_excluded: &[GeneratedFile],
+    function_hotspots: &[FunctionHotspot],
+    knowledge_risks: &[AuthorRisk],
+    treemap: &Treemap,
 ) -> String {
     let top: Vec<&HotspotResult> = results.iter().take(25).collect();
 
-    let chart_labels = serde_json::to_string(
-        &top.iter()
-            .map(|r| {
-                let parts: Vec<&str> = r.file.split('/').collect();
-                parts
-                    .iter()
-                    .rev()
-                    .take(2)
-                    .rev()
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join("/")
-            })
-            .collect::<Vec<_>>(),
-    )
-    .unwrap_or_default();
-    let chart_scores = serde_json::to_string(
-        &top.iter()
-            .map(|r| r.hotspot_score.round() as u64)
-            .collect::<Vec<_>>(),
-    )
-    .unwrap_or_default();
-    let chart_colors =
-        serde_json::to_string(&top.iter().map(|r| tier_color(&r.tier)).collect::<Vec<_>>())
-            .unwrap_or_default();
-    let full_labels =
-        serde_json::to_string(&top.iter().map(|r| r.file.as_str()).collect::<Vec<_>>())
-            .unwrap_or_default();
+    let bar_chart = bar_chart_svg(&top);
+    // Churn × complexity scatter; one point per analyzed file.
+    let quadrant_chart = scatter_svg(results);
+
+    let treemap_section = if treemap.files.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<div class=\"card\"><h2>Hotspot Treemap</h2>\
+             <p class=\"meta\">Every scored file, nested by directory. Area: {}; color: hotspot score. \
+             Hover a rectangle for details.</p><div class=\"treemap-wrap\">{}</div></div>",
+            treemap.size_by.label(),
+            super::treemap::treemap_svg(treemap, None)
+        )
+    };
 
     let crit_count: usize = results.iter().filter(|r| r.tier == Tier::Critical).count();
     let high_count: usize = results.iter().filter(|r| r.tier == Tier::High).count();
@@ -67,36 +223,201 @@ fn build_html(
 
     let security_section = if !security_risks.is_empty() {
         let rows: String = security_risks.iter().map(|r| format!(
-            "<tr><td class=\"path\">{}</td><td><span class=\"badge badge-critical\">{}</span></td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
-            esc(&r.file), esc(&r.risk_type), r.commit_count, esc(&r.first_seen), esc(&r.last_seen)
+            "<tr><td class=\"path\">{}</td><td><span class=\"badge badge-critical\">{}</span></td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+             <td>{}</td><td class=\"path\">{}</td><td class=\"path\">{}</td><td class=\"path\">{}</td><td>{}</td></tr>",
+            esc(&r.file), esc(&r.risk_type), severity_badge(&r.severity), r.commit_count, esc(&r.first_seen), esc(&r.last_seen),
+            if r.present_at_head { "<span class=\"warn\">yes</span>" } else { "<span class=\"dim\">no</span>" },
+            esc(&ref_list(&r.refs)), esc(&short_hashes(&r.added_in)), esc(&short_hashes(&r.removed_in)),
+            esc(&r.authors.join(", "))
         )).collect();
         format!(
             "<div class=\"card security-card\"><h2>🔐 Security Risks</h2>\
              <p class=\"security-note\">Sensitive files found in git history. Even deleted files remain accessible via <code>git log</code>.</p>\
-             <table><thead><tr><th>File</th><th>Risk Type</th><th style=\"text-align:right\">Commits</th><th>First Seen</th><th>Last Seen</th></tr></thead>\
+             <table class=\"sortable\"><thead><tr><th>File</th><th>Risk Type</th><th>Severity</th><th style=\"text-align:right\">Commits</th><th>First Seen</th><th>Last Seen</th>\
+             <th>At HEAD</th><th>Refs</th><th>Added In</th><th>Removed In</th><th>Authors</th></tr></thead>\
+             <tbody>{rows}</tbody></table></div>"
+        )
+    } else {
+        String::new()
+    };
+
+    let secrets_stat = if !secret_findings.is_empty() {
+        format!(
+            r#"<div class="stat" style="border-color:#ef4444"><div class="stat-label" style="color:#f87171">Secrets in History</div><div class="stat-value red">{}</div></div>"#,
+            secret_findings.len()
+        )
+    } else {
+        String::new()
+    };
+
+    let secrets_section = if !secret_findings.is_empty() {
+        let rows: String = secret_findings.iter().map(|f| format!(
+            "<tr><td class=\"path\">{}:{}</td><td><span class=\"badge badge-critical\">{}</span></td><td class=\"path\">{}</td><td class=\"path\">{}</td><td>{}</td><td>{}</td></tr>",
+            esc(&f.file), f.line, esc(&f.rule), esc(&f.redacted),
+            esc(&f.commit[..f.commit.len().min(8)]), esc(&f.date),
+            if f.present_at_head { "<span class=\"warn\">still at HEAD</span>" } else { "<span class=\"dim\">removed</span>" }
+        )).collect();
+        format!(
+            "<div class=\"card security-card\"><h2>🔑 Secrets in History</h2>\
+             <p class=\"security-note\">Credentials found in the content of committed lines. Values are redacted. Rotate every credential listed, even if it has since been removed.</p>\
+             <table class=\"sortable\"><thead><tr><th>Location</th><th>Rule</th><th>Value</th><th>Commit</th><th>Date</th><th>Status</th></tr></thead>\
+             <tbody>{rows}</tbody></table></div>"
+        )
+    } else {
+        String::new()
+    };
+
+    let suppressed_note = if suppressed > 0 {
+        format!(
+            "<p class=\"meta\">ℹ {suppressed} security finding(s) suppressed by the config allowlist.</p>"
+        )
+    } else {
+        String::new()
+    };
+
+    let functions_section = if !function_hotspots.is_empty() {
+        let rows: String = function_hotspots
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                format!(
+                    "<tr><td class=\"num\">{}</td><td class=\"path\"><strong>{}</strong></td><td class=\"path\">{}</td>\
+                     <td class=\"num\"><strong>{}</strong></td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                    i + 1,
+                    esc(&f.function),
+                    esc(&f.file),
+                    f.hotspot_score.round() as u64,
+                    f.commit_count,
+                    f.bug_commits,
+                    f.revert_count
+                )
+            })
+            .collect();
+        format!(
+            "<div class=\"card\"><h2>🔬 Function Hotspots</h2>\
+             <table class=\"sortable\"><thead><tr><th>#</th><th>Function</th><th>File</th><th style=\"text-align:right\">Score</th>\
+             <th style=\"text-align:right\">Commits</th><th style=\"text-align:right\">Bug Commits</th><th style=\"text-align:right\">Reverts</th></tr></thead>\
              <tbody>{rows}</tbody></table></div>"
         )
     } else {
         String::new()
     };
 
+    let orphan_rows: String = results
+        .iter()
+        .filter_map(|r| Some((r, r.details.ownership.as_ref().filter(|o| o.orphaned)?)))
+        .map(|(r, o)| {
+            format!(
+                "<tr><td class=\"path\">{}</td><td class=\"num\">{}</td><td>{}</td><td class=\"num\">{}d</td></tr>",
+                esc(&r.file),
+                r.hotspot_score.round() as u64,
+                esc(&o.primary_authors.join(", ")),
+                o.primary_last_active_days
+            )
+        })
+        .collect();
+    let risk_rows: String = knowledge_risks
+        .iter()
+        .take(15)
+        .map(|k| {
+            let shown: Vec<String> = k.files.iter().take(5).map(|f| esc(f)).collect();
+            let more = k.files.len() - shown.len();
+            format!(
+                "<tr><td>{}{}</td><td class=\"num\">{}</td><td class=\"path\">{}{}</td></tr>",
+                esc(&k.author),
+                if k.inactive {
+                    " <span class=\"warn\">inactive</span>"
+                } else {
+                    ""
+                },
+                k.files.len(),
+                shown.join(", "),
+                if more > 0 {
+                    format!(" <span class=\"dim\">+{more} more</span>")
+                } else {
+                    String::new()
+                }
+            )
+        })
+        .collect();
+    let knowledge_section = if !orphan_rows.is_empty() || !risk_rows.is_empty() {
+        let orphans = if orphan_rows.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<h2>👻 Orphaned Hotspots</h2>\
+                 <p class=\"meta\">Every primary author of these files has stopped committing.</p>\
+                 <table class=\"sortable\"><thead><tr><th>File</th><th style=\"text-align:right\">Score</th><th>Primary Authors</th>\
+                 <th style=\"text-align:right\">Last Active</th></tr></thead><tbody>{orphan_rows}</tbody></table>"
+            )
+        };
+        let risks = if risk_rows.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<h2>👤 Files at Risk if One Author Leaves</h2>\
+                 <p class=\"meta\">Files where the author is the only primary author.</p>\
+                 <table class=\"sortable\"><thead><tr><th>Author</th><th style=\"text-align:right\">Files</th><th>Most Active Files</th></tr></thead>\
+                 <tbody>{risk_rows}</tbody></table>"
+            )
+        };
+        format!("<div class=\"card\">{orphans}{risks}</div>")
+    } else {
+        String::new()
+    };
+
+    let excluded_section = if !auto_excluded.is_empty() {
+        let rows: String = auto_excluded
+            .iter()
+            .map(|g| {
+                format!(
+                    "<tr><td class=\"path\">{}</td><td class=\"dim\">{}</td></tr>",
+                    esc(&g.file),
+                    esc(&g.reason)
+                )
+            })
+            .collect();
+        format!(
+            "<div class=\"card\"><h2>Auto-excluded Files ({})</h2>\
+             <p class=\"meta\">Generated, minified or vendored code detected at HEAD and left out of scoring.</p>\
+             <table class=\"sortable\"><thead><tr><th>File</th><th>Reason</th></tr></thead><tbody>{rows}</tbody></table></div>",
+            auto_excluded.len()
+        )
+    } else {
+        String::new()
+    };
+
     let table_rows: String = results.iter().enumerate().map(|(i, r)| {
         let wip_cell = if r.details.wip_commits > 0 {
             format!("<span class=\"warn\">{}</span>", r.details.wip_commits)
         } else { "0".to_string() };
         format!(
-            "<tr><td class=\"num\">{}</td><td class=\"path\">{}</td><td class=\"num\"><strong>{}</strong></td>\
+            "<tr data-i=\"{i}\" tabindex=\"0\"><td class=\"num\">{}</td><td class=\"path\">{}</td><td class=\"num\"><strong>{}</strong></td>\
              <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
              <td class=\"num\">{}</td><td class=\"num\"><span class=\"dim\">{}</span></td>\
-             <td>{} <span class=\"dim\">({}%)</span></td><td>{}</td></tr>",
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td>\
+             <td>{} <span class=\"dim\">({}%)</span></td><td data-v=\"{}\">{}</td></tr>",
             i + 1, esc(&r.file), r.hotspot_score.round() as u64,
             r.details.commit_count, r.details.bug_commits, r.details.revert_count,
             wip_cell, r.details.large_commit_count,
+            r.details.loc, r.complexity_score.round() as u64, quadrant_label(&r.quadrant),
+            tests_label(r.details.tests.as_ref()),
+            coverage_label(r.line_coverage, &r.tier),
+            age_label(r),
             esc(&r.details.top_author), r.details.top_author_percent.round(),
-            tier_badge(&r.tier)
+            r.hotspot_score.round() as u64, tier_badge(&r.tier)
         )
     }).collect();
 
+    // `<` is escaped so the JSON can never close its script element.
+    let data = serde_json::json!({ "weights": weights, "results": results })
+        .to_string()
+        .replace('<', "\\u003c");
+    let script_hash = {
+        use sha2::{Digest, Sha256};
+        base64(&Sha256::digest(SCRIPT.as_bytes()))
+    };
+
     let coupling_section = if !couplings.is_empty() {
         let rows: String = couplings.iter().take(15).map(|c| format!(
             "<tr><td class=\"path\">{}</td><td class=\"path\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}%</td></tr>",
@@ -105,13 +426,45 @@ fn build_html(
         format!(
             "<div class=\"card\"><h2>⚠️ Co-change Coupling</h2>\
              <p style=\"color:#64748b;font-size:.8rem;margin-bottom:1rem\">Files that frequently change together — hidden dependencies that lead to bugs.</p>\
-             <table><thead><tr><th>File A</th><th>File B</th><th style=\"text-align:right\">Co-changes</th><th style=\"text-align:right\">Coupling Strength</th></tr></thead>\
+             <table class=\"sortable\"><thead><tr><th>File A</th><th>File B</th><th style=\"text-align:right\">Co-changes</th><th style=\"text-align:right\">Coupling Strength</th></tr></thead>\
              <tbody>{rows}</tbody></table></div>"
         )
     } else {
         String::new()
     };
 
+    let boundary_section = if !boundary_violations.is_empty() {
+        let rows: String = boundary_violations.iter().take(15).map(|c| format!(
+            "<tr><td>{}</td><td class=\"path\">{}</td><td class=\"path\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}%</td></tr>",
+            esc(c.boundary.as_deref().unwrap_or_default()), esc(&c.file_a), esc(&c.file_b), c.co_changes, c.strength.round()
+        )).collect();
+        format!(
+            "<div class=\"card\"><h2>🚧 Boundary Violations</h2>\
+             <p class=\"meta\">Files in different components that change together without a declared dependency.</p>\
+             <table class=\"sortable\"><thead><tr><th>Boundary</th><th>File A</th><th>File B</th><th style=\"text-align:right\">Co-changes</th>\
+             <th style=\"text-align:right\">Coupling Strength</th></tr></thead><tbody>{rows}</tbody></table></div>"
+        )
+    } else {
+        String::new()
+    };
+
+    let cluster_section = if !clusters.is_empty() {
+        let rows: String = clusters.iter().take(10).map(|c| format!(
+            "<tr><td class=\"path\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}%</td><td>{}</td><td class=\"path\">{}</td></tr>",
+            esc(&c.name), c.files.len(), c.cohesion.round(),
+            if c.cross_component { "<span class=\"warn\">yes</span>" } else { "no" },
+            c.files.iter().map(|f| esc(f)).collect::<Vec<_>>().join("<br>")
+        )).collect();
+        format!(
+            "<div class=\"card\"><h2>🔗 Co-change Clusters</h2>\
+             <p class=\"meta\">Groups of files linked by strong coupling. Cohesion is the average coupling strength over every pair of members.</p>\
+             <table class=\"sortable\"><thead><tr><th>Cluster</th><th style=\"text-align:right\">Files</th><th style=\"text-align:right\">Cohesion</th>\
+             <th>Spans Components</th><th>Members</th></tr></thead><tbody>{rows}</tbody></table></div>"
+        )
+    } else {
+        String::new()
+    };
+
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     format!(
@@ -120,36 +473,9 @@ fn build_html(
 <head>
   <meta charset="UTF-8"><meta name="viewport" content="width=device-width, initial-scale=1.0">
   <title>git-scanline report</title>
-  <script src="https://cdn.jsdelivr.net/npm/chart.js@4.4.0/dist/chart.umd.min.js"></script>
+  <meta http-equiv="Content-Security-Policy" content="default-src 'none'; style-src 'unsafe-inline'; script-src 'sha256-{script_hash}'">
   <style>
-    *,*::before,*::after{{box-sizing:border-box;margin:0;padding:0}}
-    body{{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;background:#0f172a;color:#e2e8f0;padding:2rem;font-size:14px;line-height:1.6}}
-    h1{{font-size:1.75rem;font-weight:800}} h1 .fire{{color:#f97316}}
-    h2{{font-size:1rem;font-weight:700;color:#94a3b8;text-transform:uppercase;letter-spacing:.06em;margin-bottom:1rem}}
-    .meta{{color:#64748b;margin:.4rem 0 2rem;font-size:.8rem}} .meta span{{margin-right:1.5rem}}
-    .stats{{display:grid;grid-template-columns:repeat(auto-fit,minmax(160px,1fr));gap:1rem;margin-bottom:1.5rem}}
-    .stat{{background:#1e293b;border:1px solid #334155;border-radius:.625rem;padding:1rem 1.25rem}}
-    .stat-label{{font-size:.7rem;text-transform:uppercase;letter-spacing:.06em;color:#64748b}}
-    .stat-value{{font-size:2rem;font-weight:800;margin-top:.2rem}}
-    .stat-value.red{{color:#f87171}} .stat-value.orange{{color:#fb923c}} .stat-value.blue{{color:#60a5fa}} .stat-value.slate{{color:#94a3b8}}
-    .card{{background:#1e293b;border:1px solid #334155;border-radius:.75rem;padding:1.5rem;margin-bottom:1.5rem}}
-    .security-card{{border-color:#ef4444}}
-    .security-note{{color:#fca5a5;font-size:.8rem;margin-bottom:1rem}}
-    .security-note code{{background:#1a0e0e;padding:.1rem .3rem;border-radius:.2rem;font-size:.75rem}}
-    .chart-wrap{{position:relative;height:280px}}
-    table{{width:100%;border-collapse:collapse}}
-    th{{text-align:left;padding:.5rem .75rem;border-bottom:2px solid #334155;font-size:.7rem;font-weight:700;text-transform:uppercase;letter-spacing:.06em;color:#64748b}}
-    td{{padding:.5rem .75rem;border-bottom:1px solid #1a2744;vertical-align:middle}}
-    tr:last-child td{{border-bottom:none}} tr:hover td{{background:#162032}}
-    td.path{{font-family:'JetBrains Mono','Fira Code',ui-monospace,monospace;font-size:.78rem;color:#7dd3fc;word-break:break-all}}
-    td.num{{text-align:right;color:#94a3b8}}
-    .dim{{color:#475569;font-size:.8em}} .warn{{color:#fbbf24;font-weight:600}}
-    .badge{{display:inline-block;padding:.15rem .5rem;border-radius:999px;font-size:.7rem;font-weight:700;white-space:nowrap}}
-    .badge-critical{{background:rgba(239,68,68,.15);color:#fca5a5}}
-    .badge-high{{background:rgba(249,115,22,.15);color:#fdba74}}
-    .badge-medium{{background:rgba(234,179,8,.15);color:#fde047}}
-    .badge-low{{background:rgba(34,197,94,.15);color:#86efac}}
-    .footer{{text-align:center;color:#334155;font-size:.75rem;margin-top:2rem}}
+{style}
   </style>
 </head>
 <body>
@@ -167,38 +493,47 @@ fn build_html(
     <div class="stat"><div class="stat-label">Total Commits</div><div class="stat-value blue">{commits}</div></div>
     <div class="stat"><div class="stat-label">Bug-fix Commits</div><div class="stat-value slate">{bug_commits}</div></div>
     {security_stat}
+    {secrets_stat}
   </div>
   {security_section}
-  <div class="card"><h2>Top Hotspot Files — Score (0–100)</h2><div class="chart-wrap"><canvas id="chart"></canvas></div></div>
+  {secrets_section}
+  {suppressed_note}
+  <div class="card"><h2>Top Hotspot Files — Score (0–100)</h2><div class="chart-wrap">{bar_chart}</div></div>
+  <div class="card"><h2>Churn × Complexity</h2><p class="meta">Top-right: changed often <em>and</em> hard to change — refactor these first.</p><div class="chart-wrap">{quadrant_chart}</div></div>
+  {treemap_section}
   <div class="card">
     <h2>Hotspot Details</h2>
-    <table>
+    <p class="meta">Click a row for its score breakdown, commits, coupled files and authors. Click a column header to sort.</p>
+    <div class="filters">
+      <input id="filter" type="search" placeholder="Filter by path…" aria-label="Filter by path">
+      <select id="tier-filter" aria-label="Filter by risk">
+        <option value="">All risk levels</option><option value="Critical">Critical</option>
+        <option value="High">High</option><option value="Medium">Medium</option><option value="Low">Low</option>
+      </select>
+    </div>
+    <table id="hotspots" class="sortable">
       <thead><tr><th>#</th><th>File</th><th style="text-align:right">Score</th><th style="text-align:right">Commits</th>
       <th style="text-align:right">Bug Commits</th><th style="text-align:right">Reverts</th>
       <th style="text-align:right">WIP</th><th style="text-align:right">Large</th>
-      <th>Top Author</th><th>Risk</th></tr></thead>
+      <th style="text-align:right">LOC</th><th style="text-align:right">Complexity</th><th>Quadrant</th>
+      <th>Tests</th><th style="text-align:right">Coverage</th><th>Age</th><th>Top Author</th><th>Risk</th></tr></thead>
       <tbody>{table_rows}</tbody>
     </table>
   </div>
+  {functions_section}
   {coupling_section}
+  {boundary_section}
+  {cluster_section}
+  {knowledge_section}
+  {excluded_section}
   <p class="footer">Generated by git-scanline on {now}</p>
-  <script>
-    const ctx = document.getElementById('chart');
-    new Chart(ctx, {{
-      type: 'bar',
-      data: {{ labels: {chart_labels}, datasets: [{{ label: 'Hotspot Score', data: {chart_scores}, backgroundColor: {chart_colors}, borderRadius: 4, borderSkipped: false }}] }},
-      options: {{
-        responsive: true, maintainAspectRatio: false,
-        plugins: {{ legend: {{ display: false }}, tooltip: {{ callbacks: {{ title: items => {full_labels}[items[0].dataIndex], label: items => ' Score: ' + items.raw }} }} }},
-        scales: {{
-          y: {{ beginAtZero: true, max: 100, ticks: {{ color: '#64748b' }}, grid: {{ color: '#1e2d47' }} }},
-          x: {{ ticks: {{ color: '#64748b', maxRotation: 40, font: {{ size: 11 }} }}, grid: {{ display: false }} }}
-        }}
-      }}
-    }});
-  </script>
+  <aside id="detail" hidden></aside>
+  <script type="application/json" id="scanline-data">{data}</script>
+  <script>{script}</script>
 </body>
 </html>"#,
+        style = STYLE,
+        script = SCRIPT,
         since = esc(&meta.since),
         repo = esc(&meta.repo_path),
         now = now,
@@ -209,21 +544,219 @@ fn build_html(
         bug_commits = total_bug_commits,
         security_stat = security_stat,
         security_section = security_section,
+        secrets_stat = secrets_stat,
+        secrets_section = secrets_section,
+        suppressed_note = suppressed_note,
         table_rows = table_rows,
         coupling_section = coupling_section,
-        chart_labels = chart_labels,
-        chart_scores = chart_scores,
-        chart_colors = chart_colors,
-        full_labels = full_labels,
+        boundary_section = boundary_section,
+        cluster_section = cluster_section,
+        knowledge_section = knowledge_section,
+        excluded_section = excluded_section,
+        functions_section = functions_section,
+        bar_chart = bar_chart,
+        quadrant_chart = quadrant_chart,
+    )
+}
+
+/// Writes the `authors` report as a standalone HTML page.
+pub fn report_authors_html(report: &AuthorReport, output_file: &Path) -> Result<(), String> {
+    let meta = &report.meta;
+    let by_team = report.group_by == "team";
+    let rows: String = report
+        .authors
+        .iter()
+        .map(|a| {
+            let members = if by_team {
+                format!(
+                    "<br><span class=\"dim\">{}</span>",
+                    esc(&a.members.join(", "))
+                )
+            } else {
+                String::new()
+            };
+            let wip = format!("{}%", (a.wip_ratio * 100.0).round());
+            format!(
+                "<tr><td>{}{members}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\"><strong>{}</strong></td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}</td><td class=\"num\">{:.1}</td></tr>",
+                esc(&a.name),
+                a.commits,
+                a.files_touched,
+                a.hotspots_owned,
+                a.bug_fix_commits,
+                a.reverts_authored,
+                if a.wip_ratio >= 0.25 {
+                    format!("<span class=\"warn\">{wip}</span>")
+                } else {
+                    wip
+                },
+                a.avg_commit_size
+            )
+        })
+        .collect();
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8"><meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>git-scanline authors</title>
+  <style>
+{style}
+  </style>
+</head>
+<body>
+  <h1><span class="fire">🔥</span> git-scanline — {title}</h1>
+  <p class="meta">
+    <span>Since: <strong>{since}</strong></span>
+    <span>Repo: <strong>{repo}</strong></span>
+    <span>Generated: <strong>{now}</strong></span>
+    <span>Commits: <strong>{commits}</strong></span>
+    <span>Files scanned: <strong>{file_count}</strong></span>
+  </p>
+  <div class="card">
+    <table>
+      <thead><tr><th>{column}</th><th style="text-align:right">Commits</th><th style="text-align:right">Files</th>
+      <th style="text-align:right">Hotspots Owned</th><th style="text-align:right">Bug Fixes</th><th style="text-align:right">Reverts</th>
+      <th style="text-align:right">WIP</th><th style="text-align:right">Avg Files / Commit</th></tr></thead>
+      <tbody>{rows}</tbody>
+    </table>
+  </div>
+  <p class="footer">Generated by git-scanline on {now}</p>
+</body>
+</html>"#,
+        style = STYLE,
+        title = if by_team { "Teams" } else { "Authors" },
+        column = if by_team { "Team" } else { "Author" },
+        since = esc(&meta.since),
+        repo = esc(&meta.repo_path),
+        commits = meta.commit_count,
+        file_count = meta.file_count,
+    );
+    fs::write(output_file, &html)
+        .map_err(|e| format!("Failed to write {}: {e}", output_file.display()))?;
+    eprintln!("✓ HTML report written to {}", output_file.display());
+    Ok(())
+}
+
+// Chart geometry, in SVG user units; the SVG scales to the card width.
+const CHART_W: f64 = 1000.0;
+const CHART_H: f64 = 320.0;
+const PLOT_LEFT: f64 = 44.0;
+const PLOT_TOP: f64 = 12.0;
+
+/// Horizontal grid lines and labels at 0, 25, 50, 75 and 100 on a 0–100 axis.
+fn y_grid(plot_bottom: f64, plot_right: f64) -> String {
+    let plot_h = plot_bottom - PLOT_TOP;
+    (0..=4)
+        .map(|i| {
+            let y = plot_bottom - plot_h * f64::from(i) / 4.0;
+            format!(
+                "<line x1=\"{PLOT_LEFT}\" y1=\"{y:.1}\" x2=\"{plot_right}\" y2=\"{y:.1}\" stroke=\"#1e2d47\"/>\
+                 <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+                PLOT_LEFT - 8.0,
+                y + 4.0,
+                i * 25
+            )
+        })
+        .collect()
+}
+
+/// Bar chart of hotspot scores, colored by tier; each bar's tooltip shows the full path.
+fn bar_chart_svg(top: &[&HotspotResult]) -> String {
+    let plot_bottom = CHART_H - 100.0;
+    let plot_right = CHART_W - 8.0;
+    let slot = (plot_right - PLOT_LEFT) / top.len().max(1) as f64;
+    let bars: String = top
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let score = r.hotspot_score.round().clamp(0.0, 100.0);
+            let h = (plot_bottom - PLOT_TOP) * score / 100.0;
+            let x = PLOT_LEFT + slot * i as f64 + slot * 0.15;
+            let cx = PLOT_LEFT + slot * (i as f64 + 0.5);
+            let label: Vec<&str> = r.file.rsplit('/').take(2).collect();
+            let label: Vec<&str> = label.into_iter().rev().collect();
+            format!(
+                "<g><title>{file} — score {score}</title>\
+                 <rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{h:.1}\" rx=\"4\" fill=\"{}\"/>\
+                 <text transform=\"translate({cx:.1},{:.1}) rotate(-40)\" text-anchor=\"end\">{}</text></g>",
+                plot_bottom - h,
+                slot * 0.7,
+                tier_color(&r.tier),
+                plot_bottom + 14.0,
+                esc(&label.join("/")),
+                file = esc(&r.file),
+            )
+        })
+        .collect();
+    format!(
+        "<svg viewBox=\"0 0 {CHART_W} {CHART_H}\" role=\"img\" aria-label=\"Hotspot scores\">{}{bars}</svg>",
+        y_grid(plot_bottom, plot_right)
     )
 }
 
-fn esc(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#x27;")
+/// Churn (y) against complexity (x), both 0–100; each point's tooltip names the file.
+fn scatter_svg(results: &[HotspotResult]) -> String {
+    let plot_bottom = CHART_H - 40.0;
+    let plot_right = CHART_W - 12.0;
+    let (plot_w, plot_h) = (plot_right - PLOT_LEFT, plot_bottom - PLOT_TOP);
+    let points: String = results
+        .iter()
+        .map(|r| {
+            let (cx, cy) = (
+                r.complexity_score.round().clamp(0.0, 100.0),
+                r.churn_score.round().clamp(0.0, 100.0),
+            );
+            format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"5\" fill=\"#f97316\" fill-opacity=\"0.8\">\
+                 <title>{} — complexity {cx}, churn {cy}</title></circle>",
+                PLOT_LEFT + plot_w * cx / 100.0,
+                plot_bottom - plot_h * cy / 100.0,
+                esc(&r.file)
+            )
+        })
+        .collect();
+    let x_ticks: String = (0..=4)
+        .map(|i| {
+            format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+                PLOT_LEFT + plot_w * f64::from(i) / 4.0,
+                plot_bottom + 16.0,
+                i * 25
+            )
+        })
+        .collect();
+    format!(
+        "<svg viewBox=\"0 0 {CHART_W} {CHART_H}\" role=\"img\" aria-label=\"Churn versus complexity\">{}{x_ticks}\
+         <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">Complexity</text>\
+         <text transform=\"translate(12,{:.1}) rotate(-90)\" text-anchor=\"middle\">Churn</text>{points}</svg>",
+        y_grid(plot_bottom, plot_right),
+        PLOT_LEFT + plot_w / 2.0,
+        CHART_H - 4.0,
+        PLOT_TOP + plot_h / 2.0
+    )
+}
+
+/// Standard base64 with padding, for the CSP script hash.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
 }
 
 fn tier_color(tier: &Tier) -> &'static str {
@@ -235,6 +768,73 @@ fn tier_color(tier: &Tier) -> &'static str {
     }
 }
 
+fn short_hashes(hashes: &[String]) -> String {
+    hashes
+        .iter()
+        .map(|h| &h[..h.len().min(8)])
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn quadrant_label(q: &Quadrant) -> &'static str {
+    match q {
+        Quadrant::Hotspot => "<span class=\"warn\">hotspot</span>",
+        Quadrant::ActiveSimple => "<span class=\"dim\">active-simple</span>",
+        Quadrant::StableComplex => "<span class=\"dim\">stable-complex</span>",
+        Quadrant::Healthy => "<span class=\"dim\">healthy</span>",
+    }
+}
+
+/// Share of commits that also changed the tests, "none" without tests.
+fn tests_label(tests: Option<&TestCoevolution>) -> String {
+    match tests {
+        None => "<span class=\"dim\">—</span>".to_string(),
+        Some(t) => match t.co_change_ratio {
+            Some(ratio) => format!("{}%", (ratio * 100.0).round()),
+            None if !t.test_files.is_empty() => "0%".to_string(),
+            None if t.inline_tests => "<span class=\"dim\">inline</span>".to_string(),
+            None => "<span class=\"warn\">none</span>".to_string(),
+        },
+    }
+}
+
+/// Line coverage, flagged on critical hotspots below 40%.
+fn coverage_label(coverage: Option<f64>, tier: &Tier) -> String {
+    match coverage {
+        None => "<span class=\"dim\">—</span>".to_string(),
+        Some(c) if *tier == Tier::Critical && c < 40.0 => {
+            format!("<span class=\"warn\">{}%</span>", c.round())
+        }
+        Some(c) => format!("{}%", c.round()),
+    }
+}
+
+/// Lifecycle class and days since the last change; dampened scores are marked.
+fn age_label(r: &HotspotResult) -> String {
+    let Some(age) = &r.details.age else {
+        return "<span class=\"dim\">—</span>".to_string();
+    };
+    let dampened = if r.stability_dampened {
+        " <span class=\"dim\">(dampened)</span>"
+    } else {
+        ""
+    };
+    format!(
+        "{} <span class=\"dim\">{}d</span>{dampened}",
+        age.age_class, age.last_modified_days
+    )
+}
+
+fn severity_badge(severity: &str) -> String {
+    let class = match severity {
+        "critical" => "badge-critical",
+        "high" => "badge-high",
+        "medium" => "badge-medium",
+        _ => "badge-low",
+    };
+    format!("<span class=\"badge {class}\">{}</span>", esc(severity))
+}
+
 fn tier_badge(tier: &Tier) -> &'static str {
     match tier {
         Tier::Critical => "<span class=\"badge badge-critical\">🔴 CRITICAL</span>",
@@ -243,3 +843,136 @@ fn tier_badge(tier: &Tier) -> &'static str {
         Tier::Low => "<span class=\"badge badge-low\">🟢 LOW</span>",
     }
 }
+
+/// Writes the `coupled` query as a standalone HTML page.
+pub fn report_coupled_html(report: &CouplingQuery, output_file: &Path) -> Result<(), String> {
+    let meta = &report.meta;
+    let rows: String = report
+        .coupled
+        .iter()
+        .map(|c| {
+            format!(
+                "<tr><td class=\"path\">{}</td><td class=\"num\"><strong>{}%</strong></td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}%</td><td class=\"num\">{}%</td></tr>",
+                esc(&c.file),
+                c.confidence.round(),
+                c.support,
+                c.reverse_confidence.round(),
+                c.strength.round()
+            )
+        })
+        .collect();
+    let window = report
+        .window_hours
+        .map(|h| format!("<span>Window: <strong>{h}h per author</strong></span>"))
+        .unwrap_or_default();
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8"><meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>git-scanline coupled</title>
+  <style>
+{style}
+  </style>
+</head>
+<body>
+  <h1><span class="fire">🔥</span> git-scanline — Changes with {file}</h1>
+  <p class="meta">
+    <span>Since: <strong>{since}</strong></span>
+    <span>Repo: <strong>{repo}</strong></span>
+    <span>Generated: <strong>{now}</strong></span>
+    <span>Change sets: <strong>{change_sets}</strong></span>
+    {window}
+  </p>
+  <div class="card">
+    <p class="meta">Confidence: share of this file's changes that also touched the other file. Reverse: the other way round.</p>
+    <table>
+      <thead><tr><th>File</th><th style="text-align:right">Confidence</th><th style="text-align:right">Support</th>
+      <th style="text-align:right">Reverse</th><th style="text-align:right">Strength</th></tr></thead>
+      <tbody>{rows}</tbody>
+    </table>
+  </div>
+  <p class="footer">Generated by git-scanline on {now}</p>
+</body>
+</html>"#,
+        style = STYLE,
+        file = esc(&report.file),
+        since = esc(&meta.since),
+        repo = esc(&meta.repo_path),
+        change_sets = report.change_sets,
+    );
+    fs::write(output_file, &html)
+        .map_err(|e| format!("Failed to write {}: {e}", output_file.display()))?;
+    eprintln!("✓ HTML report written to {}", output_file.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_base64_rfc4648_vectors() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(input.as_bytes()), expected, "base64({input:?})");
+        }
+        assert_eq!(
+            base64(&[0xfb, 0xff, 0xbf]),
+            "+/+/",
+            "Last two alphabet chars"
+        );
+        assert_eq!(
+            base64(&Sha256::digest(b"abc")),
+            "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0="
+        );
+    }
+
+    #[test]
+    fn test_csp_hash_matches_inline_script() {
+        let meta = ReportMeta {
+            since: "all history".to_string(),
+            commit_count: 0,
+            file_count: 0,
+            analyzed_at: "2024-05-01T00:00:00Z".to_string(),
+            repo_path: "/src/app".to_string(),
+        };
+        let html = build_html(
+            &meta,
+            &Weights::default(),
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            0,
+            &[],
+            &[],
+            &[],
+            &Treemap::default(),
+        );
+        let csp_hash = html
+            .split("script-src 'sha256-")
+            .nth(1)
+            .and_then(|rest| rest.split('\'').next())
+            .expect("CSP declares a script hash");
+        let script = html
+            .split("<script>")
+            .nth(1)
+            .and_then(|rest| rest.split("</script>").next())
+            .expect("inline script present");
+        assert_eq!(script, SCRIPT);
+        assert_eq!(csp_hash, base64(&Sha256::digest(script.as_bytes())));
+    }
+}
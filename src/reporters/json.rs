@@ -1,10 +1,10 @@
-use crate::types::Report;
+use serde::Serialize;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-/// Outputs the report as JSON. Writes to a file if given, otherwise stdout.
-pub fn report_json(report: &Report, output_file: Option<&Path>) -> Result<(), String> {
+/// Outputs a report as JSON. Writes to a file if given, otherwise stdout.
+pub fn report_json<T: Serialize>(report: &T, output_file: Option<&Path>) -> Result<(), String> {
     if let Some(path) = output_file {
         let file = File::create(path)
             .map_err(|e| format!("Failed to open {} for writing: {e}", path.display()))?;
@@ -0,0 +1,440 @@
+use super::common::build_recommendations;
+use crate::types::{Report, Tier};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Smallest accepted `--markdown-max-chars`; below this not even the header fits.
+pub const MIN_MAX_CHARS: usize = 1000;
+
+/// Hotspot rows kept before security sections are trimmed.
+const MIN_HOTSPOT_ROWS: usize = 10;
+
+// Section positions in `build_markdown`.
+const HOTSPOTS: usize = 0;
+const SECURITY: usize = 1;
+const SECRETS: usize = 2;
+const RECOMMENDATIONS: usize = 3;
+const BOUNDARIES: usize = 4;
+const COUPLING: usize = 5;
+
+/// Section and rows to keep, trimmed in this order until the report fits.
+const TRIM_ORDER: [(usize, usize); 7] = [
+    (COUPLING, 0),
+    (BOUNDARIES, 0),
+    (RECOMMENDATIONS, 0),
+    (HOTSPOTS, MIN_HOTSPOT_ROWS),
+    (SECRETS, 0),
+    (SECURITY, 0),
+    (HOTSPOTS, 1),
+];
+
+const FOOTER: &str = "<sub>Generated by git-scanline</sub>\n";
+
+/// A block of the report whose rows can be dropped to fit the size limit.
+struct Section {
+    /// `<summary>` text; `None` renders the rows in the open.
+    summary: Option<String>,
+    /// Table header lines, or empty for a bullet list.
+    header: String,
+    rows: Vec<String>,
+    /// Rows dropped to fit the size limit.
+    omitted: usize,
+}
+
+impl Section {
+    fn render(&self, out: &mut String) {
+        if self.rows.is_empty() {
+            return;
+        }
+        out.push_str(&self.open());
+        for row in &self.rows {
+            out.push_str(row);
+            out.push('\n');
+        }
+        out.push_str(&self.close());
+    }
+
+    /// Text before the rows: `<details>` wrapper and table header.
+    fn open(&self) -> String {
+        match &self.summary {
+            Some(summary) => format!("<details>\n<summary>{summary}</summary>\n\n{}", self.header),
+            None => self.header.clone(),
+        }
+    }
+
+    /// Text after the rows: omitted-row note and closing wrapper.
+    fn close(&self) -> String {
+        let mut out = String::new();
+        if self.omitted > 0 {
+            out.push_str(&format!("\n_…and {} more_\n", self.omitted));
+        }
+        if self.summary.is_some() {
+            out.push_str("\n</details>\n");
+        }
+        out.push('\n');
+        out
+    }
+
+    /// Rendered length in characters, given the characters of its rows
+    /// (newlines included).
+    fn chars(&self, row_chars: usize) -> usize {
+        if self.rows.is_empty() {
+            return 0;
+        }
+        self.open().chars().count() + row_chars + self.close().chars().count()
+    }
+}
+
+/// Writes the report as GitHub-flavored Markdown, to a file if given,
+/// otherwise stdout. With `max_chars`, rows are dropped until the text fits:
+/// coupling first, then boundary violations, recommendations, hotspot rows
+/// beyond the first ten, secrets, security risks and finally the remaining
+/// hotspot rows.
+pub fn report_markdown(
+    report: &Report,
+    repo: &str,
+    max_chars: Option<usize>,
+    output_file: Option<&Path>,
+) -> Result<(), String> {
+    let text = build_markdown(report, repo, max_chars);
+    if let Some(path) = output_file {
+        fs::write(path, &text).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        eprintln!("✓ Markdown report written to {}", path.display());
+    } else {
+        std::io::stdout()
+            .lock()
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write stdout: {e}"))?;
+    }
+    Ok(())
+}
+
+fn build_markdown(report: &Report, repo: &str, max_chars: Option<usize>) -> String {
+    let meta = &report.meta;
+    let count = |tier: Tier| report.results.iter().filter(|r| r.tier == tier).count();
+    let mut head = format!(
+        "## 🔥 git-scanline — {}\n\n_Since {} · {} commits · {} files · {}_\n\n",
+        cell(repo),
+        cell(&meta.since),
+        meta.commit_count,
+        meta.file_count,
+        meta.analyzed_at.get(..10).unwrap_or(&meta.analyzed_at)
+    );
+    if report.results.is_empty() {
+        head.push_str("No hotspots found with current filters.\n\n");
+    } else {
+        head.push_str(&format!(
+            "**{}** critical · **{}** high · **{}** medium · **{}** low\n\n",
+            count(Tier::Critical),
+            count(Tier::High),
+            count(Tier::Medium),
+            count(Tier::Low)
+        ));
+    }
+
+    let mut sections = vec![
+        Section {
+            summary: None,
+            header: "| # | File | Score | Risk | Commits | Bugs | Reverts | Top author |\n\
+                     |--:|------|------:|------|--------:|-----:|--------:|------------|\n"
+                .to_string(),
+            rows: report
+                .results
+                .iter()
+                .enumerate()
+                .map(|(i, r)| {
+                    format!(
+                        "| {} | `{}` | {} | {} | {} | {} | {} | {} ({}%) |",
+                        i + 1,
+                        code(&r.file),
+                        r.hotspot_score.round(),
+                        tier_label(&r.tier),
+                        r.details.commit_count,
+                        r.details.bug_commits,
+                        r.details.revert_count,
+                        cell(&r.details.top_author),
+                        r.details.top_author_percent.round()
+                    )
+                })
+                .collect(),
+            omitted: 0,
+        },
+        Section {
+            summary: Some(format!(
+                "🔐 Security risks ({})",
+                report.security_risks.len()
+            )),
+            header: "| File | Type | Severity | Commits | Status |\n\
+                     |------|------|----------|--------:|--------|\n"
+                .to_string(),
+            rows: report
+                .security_risks
+                .iter()
+                .map(|r| {
+                    let status = if r.present_at_head {
+                        "**still at HEAD**"
+                    } else if !r.refs.is_empty() {
+                        "removed from HEAD"
+                    } else {
+                        "history only"
+                    };
+                    format!(
+                        "| `{}` | {} | {} | {} | {} |",
+                        code(&r.file),
+                        cell(&r.risk_type),
+                        cell(&r.severity),
+                        r.commit_count,
+                        status
+                    )
+                })
+                .collect(),
+            omitted: 0,
+        },
+        Section {
+            summary: Some(format!(
+                "🔑 Secrets in history ({})",
+                report.secret_findings.len()
+            )),
+            header: "| Location | Rule | Value | Commit | Status |\n\
+                     |----------|------|-------|--------|--------|\n"
+                .to_string(),
+            rows: report
+                .secret_findings
+                .iter()
+                .map(|f| {
+                    format!(
+                        "| `{}:{}` | {} | `{}` | `{}` | {} |",
+                        code(&f.file),
+                        f.line,
+                        cell(&f.rule),
+                        code(&f.redacted),
+                        &f.commit[..f.commit.len().min(8)],
+                        if f.present_at_head {
+                            "**still at HEAD**"
+                        } else {
+                            "removed from HEAD"
+                        }
+                    )
+                })
+                .collect(),
+            omitted: 0,
+        },
+        Section {
+            summary: Some("💡 Recommendations".to_string()),
+            header: String::new(),
+            rows: build_recommendations(&report.results, |name, _| format!("`{}`", code(name)))
+                .into_iter()
+                .map(|rec| format!("- {rec}"))
+                .collect(),
+            omitted: 0,
+        },
+        Section {
+            summary: Some(format!(
+                "🚧 Coupling across boundaries ({})",
+                report.boundary_violations.len()
+            )),
+            header: "| Boundary | File A | File B | Co-changes | Strength |\n\
+                     |----------|--------|--------|-----------:|---------:|\n"
+                .to_string(),
+            rows: report
+                .boundary_violations
+                .iter()
+                .map(|c| {
+                    format!(
+                        "| {} | `{}` | `{}` | {} | {}% |",
+                        cell(c.boundary.as_deref().unwrap_or_default()),
+                        code(&c.file_a),
+                        code(&c.file_b),
+                        c.co_changes,
+                        c.strength.round()
+                    )
+                })
+                .collect(),
+            omitted: 0,
+        },
+        Section {
+            summary: Some("⚠️ Co-change coupling".to_string()),
+            header: "| File A | File B | Co-changes | Strength |\n\
+                     |--------|--------|-----------:|---------:|\n"
+                .to_string(),
+            rows: report
+                .couplings
+                .iter()
+                .map(|c| {
+                    format!(
+                        "| `{}` | `{}` | {} | {}% |",
+                        code(&c.file_a),
+                        code(&c.file_b),
+                        c.co_changes,
+                        c.strength.round()
+                    )
+                })
+                .collect(),
+            omitted: 0,
+        },
+    ];
+
+    fit(&head, &mut sections, max_chars)
+}
+
+/// Renders the report, dropping rows in trimming order while it exceeds
+/// `max_chars`. `sections` are indexed as laid out in [`build_markdown`].
+/// Row sizes are counted once and the document is rendered only at the end.
+fn fit(head: &str, sections: &mut [Section], max_chars: Option<usize>) -> String {
+    let note = format!(
+        "_Trimmed to fit {} characters; run git-scanline locally for the full report._\n\n",
+        max_chars.unwrap_or_default()
+    );
+    let render = |sections: &[Section]| -> String {
+        let mut out = head.to_string();
+        for s in sections {
+            s.render(&mut out);
+        }
+        if sections.iter().any(|s| s.omitted > 0) {
+            out.push_str(&note);
+        }
+        out.push_str(FOOTER);
+        out
+    };
+    let Some(max) = max_chars else {
+        return render(sections);
+    };
+
+    let mut row_chars: Vec<usize> = sections
+        .iter()
+        .map(|s| s.rows.iter().map(|r| r.chars().count() + 1).sum())
+        .collect();
+    let fixed = head.chars().count() + FOOTER.chars().count();
+    let size = |sections: &[Section], row_chars: &[usize]| -> usize {
+        let body: usize = sections
+            .iter()
+            .zip(row_chars)
+            .map(|(s, &rows)| s.chars(rows))
+            .sum();
+        let trimmed = sections.iter().any(|s| s.omitted > 0);
+        fixed + body + if trimmed { note.chars().count() } else { 0 }
+    };
+
+    let mut total = size(sections, &row_chars);
+    let mut pass = 0;
+    while total > max && pass < TRIM_ORDER.len() {
+        let (idx, keep) = TRIM_ORDER[pass];
+        match sections.get_mut(idx) {
+            Some(section) if section.rows.len() > keep => {
+                if let Some(row) = section.rows.pop() {
+                    row_chars[idx] -= row.chars().count() + 1;
+                }
+                section.omitted += 1;
+                total = size(sections, &row_chars);
+            }
+            _ => pass += 1,
+        }
+    }
+    let mut text = render(sections);
+    debug_assert_eq!(text.chars().count(), total, "size estimate drifted");
+    if text.chars().count() > max {
+        // Only the header is left; cut it at a character boundary.
+        text = text.chars().take(max.saturating_sub(1)).collect();
+        text.push('…');
+    }
+    text
+}
+
+fn tier_label(tier: &Tier) -> &'static str {
+    match tier {
+        Tier::Critical => "🔴 Critical",
+        Tier::High => "🟠 High",
+        Tier::Medium => "🟡 Medium",
+        Tier::Low => "🟢 Low",
+    }
+}
+
+/// Plain text in a table cell: pipes would end the cell, `<` could open HTML.
+fn cell(s: &str) -> String {
+    s.replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace('\n', " ")
+}
+
+/// Text inside backticks in a table cell, where only pipes and backticks matter.
+fn code(s: &str) -> String {
+    s.replace('|', "\\|").replace('`', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(summary: Option<&str>, rows: usize) -> Section {
+        Section {
+            summary: summary.map(str::to_string),
+            header: "| File |\n|------|\n".to_string(),
+            rows: (0..rows).map(|i| format!("| `file-{i}.rs` |")).collect(),
+            omitted: 0,
+        }
+    }
+
+    fn sections() -> Vec<Section> {
+        vec![
+            section(None, 30),
+            section(Some("Security"), 5),
+            section(Some("Secrets"), 0),
+            section(Some("Recommendations"), 5),
+            section(Some("Boundaries"), 4),
+            section(Some("Coupling"), 10),
+        ]
+    }
+
+    #[test]
+    fn test_unbounded_report_keeps_everything() {
+        let text = fit("## head\n\n", &mut sections(), None);
+        assert!(text.contains("| `file-29.rs` |"));
+        assert!(text.contains("<details>\n<summary>Coupling</summary>\n\n| File |"));
+        assert!(!text.contains("Secrets"), "Empty sections are left out");
+        assert!(!text.contains("Trimmed"));
+    }
+
+    #[test]
+    fn test_size_limit_trims_lowest_priority_first() {
+        let full = fit("## head\n\n", &mut sections(), None).chars().count();
+        let mut trimmed = sections();
+        let text = fit("## head\n\n", &mut trimmed, Some(full - 200));
+        assert!(text.chars().count() <= full - 200);
+        assert!(text.contains("Trimmed to fit"));
+        assert!(trimmed[COUPLING].rows.is_empty(), "Coupling goes first");
+        assert_eq!(trimmed[HOTSPOTS].rows.len(), 30, "Hotspots are kept");
+
+        let mut tight = sections();
+        let text = fit("## head\n\n", &mut tight, Some(600));
+        assert!(text.chars().count() <= 600);
+        assert!(tight[RECOMMENDATIONS].rows.is_empty());
+        assert!(tight[HOTSPOTS].rows.len() >= MIN_HOTSPOT_ROWS);
+        assert!(
+            !tight[SECURITY].rows.is_empty(),
+            "Security outlives extra hotspot rows"
+        );
+        let omitted = tight[HOTSPOTS].omitted;
+        assert!(omitted > 0, "Extra hotspot rows go before security");
+        assert!(text.contains(&format!("_…and {omitted} more_")));
+    }
+
+    #[test]
+    fn test_boundaries_trimmed_before_recommendations() {
+        let full = fit("## head\n\n", &mut sections(), None).chars().count();
+        let mut only_boundaries_gone = false;
+        for limit in (600..full).step_by(10) {
+            let mut trimmed = sections();
+            fit("## head\n\n", &mut trimmed, Some(limit));
+            if trimmed[RECOMMENDATIONS].rows.len() < 5 {
+                assert!(
+                    trimmed[BOUNDARIES].rows.is_empty(),
+                    "Boundary rows go before recommendations (limit {limit})"
+                );
+            }
+            only_boundaries_gone |=
+                trimmed[BOUNDARIES].rows.is_empty() && trimmed[RECOMMENDATIONS].rows.len() == 5;
+        }
+        assert!(only_boundaries_gone);
+    }
+}
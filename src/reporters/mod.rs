@@ -1,3 +1,10 @@
+mod common;
+pub mod csv;
+pub mod graph;
 pub mod html;
 pub mod json;
+pub mod markdown;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod terminal;
+pub mod treemap;
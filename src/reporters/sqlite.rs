@@ -0,0 +1,348 @@
+use super::csv::{
+    coupling_row, hotspot_row, security_row, Value, COUPLING_COLUMNS, HOTSPOT_COLUMNS,
+    SECURITY_COLUMNS,
+};
+use crate::analyzers::bug_correlation::is_bug_fix;
+use crate::analyzers::revert_tracker::is_revert;
+use crate::git::log_parser::CommitChanges;
+use crate::types::{Commit, Report};
+use rusqlite::types::{Null, ToSqlOutput};
+use rusqlite::{params, params_from_iter, Connection, ToSql, Transaction};
+use std::path::Path;
+
+/// Stored in `PRAGMA user_version`; bump when a table changes shape.
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    run_id       INTEGER PRIMARY KEY AUTOINCREMENT,
+    analyzed_at  TEXT NOT NULL,
+    repo         TEXT NOT NULL,
+    repo_path    TEXT NOT NULL,
+    since        TEXT NOT NULL,
+    commit_count INTEGER NOT NULL,
+    file_count   INTEGER NOT NULL,
+    weights      TEXT NOT NULL,
+    version      TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commits (
+    hash      TEXT PRIMARY KEY,
+    author    TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    subject   TEXT NOT NULL,
+    bug_fix   INTEGER NOT NULL,
+    revert    INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commit_files (
+    hash      TEXT NOT NULL REFERENCES commits(hash),
+    file      TEXT NOT NULL,
+    additions INTEGER,
+    deletions INTEGER,
+    PRIMARY KEY (hash, file)
+);
+CREATE INDEX IF NOT EXISTS commit_files_by_file ON commit_files (file);
+CREATE TABLE IF NOT EXISTS run_commits (
+    run_id INTEGER NOT NULL REFERENCES runs(run_id),
+    hash   TEXT NOT NULL REFERENCES commits(hash),
+    PRIMARY KEY (run_id, hash)
+);
+";
+
+impl ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self {
+            Value::Null => Null.into(),
+            Value::Int(v) => (*v).into(),
+            Value::Real(v) => (*v).into(),
+            Value::Bool(v) => (*v).into(),
+            Value::Text(s) => s.as_str().into(),
+        })
+    }
+}
+
+/// Appends one run to the SQLite database at `path`, creating it and its
+/// tables if needed: run metadata and the reported file signals, couplings
+/// and security risks keyed by `run_id`, so runs accumulate over time. Commits
+/// and their per-file numstat are stored once by hash; `run_commits` links
+/// each run to the commits it analyzed.
+pub fn report_sqlite(
+    report: &Report,
+    repo: &str,
+    commits: &[Commit],
+    numstat: &CommitChanges,
+    path: &Path,
+) -> Result<(), String> {
+    let err = |e: rusqlite::Error| format!("SQLite error in {}: {e}", path.display());
+    let mut conn = Connection::open(path).map_err(err)?;
+    let run_id = write_run(&mut conn, report, repo, commits, numstat).map_err(|e| match e {
+        RunError::Sql(e) => err(e),
+        RunError::Schema(v) => format!(
+            "{} was created with an incompatible schema (version {v}, expected {SCHEMA_VERSION}); \
+             use a new database file",
+            path.display()
+        ),
+    })?;
+    eprintln!("✓ Run {run_id} written to {}", path.display());
+    Ok(())
+}
+
+enum RunError {
+    Sql(rusqlite::Error),
+    Schema(i64),
+}
+
+impl From<rusqlite::Error> for RunError {
+    fn from(e: rusqlite::Error) -> Self {
+        RunError::Sql(e)
+    }
+}
+
+fn write_run(
+    conn: &mut Connection,
+    report: &Report,
+    repo: &str,
+    commits: &[Commit],
+    numstat: &CommitChanges,
+) -> Result<i64, RunError> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version != 0 && version != SCHEMA_VERSION {
+        return Err(RunError::Schema(version));
+    }
+    let tx = conn.transaction()?;
+    tx.execute_batch(SCHEMA)?;
+    for (table, columns) in [
+        ("file_signals", HOTSPOT_COLUMNS),
+        ("couplings", COUPLING_COLUMNS),
+        ("security_risks", SECURITY_COLUMNS),
+    ] {
+        let defs: Vec<String> = columns
+            .iter()
+            .map(|(name, ty)| format!("{name} {ty}"))
+            .collect();
+        tx.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (\
+             run_id INTEGER NOT NULL REFERENCES runs(run_id), {});",
+            defs.join(", ")
+        ))?;
+    }
+    tx.execute_batch(&format!("PRAGMA user_version = {SCHEMA_VERSION};"))?;
+
+    let meta = &report.meta;
+    tx.execute(
+        "INSERT INTO runs (analyzed_at, repo, repo_path, since, commit_count, file_count, weights, version) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            meta.analyzed_at,
+            repo,
+            meta.repo_path,
+            meta.since,
+            meta.commit_count as i64,
+            meta.file_count as i64,
+            serde_json::to_string(&report.weights).unwrap_or_default(),
+            env!("CARGO_PKG_VERSION"),
+        ],
+    )?;
+    let run_id = tx.last_insert_rowid();
+
+    {
+        let mut commit_stmt = tx.prepare(
+            "INSERT OR IGNORE INTO commits (hash, author, timestamp, subject, bug_fix, revert) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        let mut file_stmt = tx.prepare(
+            "INSERT OR IGNORE INTO commit_files (hash, file, additions, deletions) \
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        let mut link_stmt =
+            tx.prepare("INSERT OR IGNORE INTO run_commits (run_id, hash) VALUES (?1, ?2)")?;
+        for c in commits {
+            let inserted = commit_stmt.execute(params![
+                c.hash,
+                c.author,
+                c.timestamp,
+                c.subject,
+                is_bug_fix(&c.subject),
+                is_revert(&c.subject),
+            ])?;
+            link_stmt.execute(params![run_id, c.hash])?;
+            // Commits are immutable: a known hash already has its files.
+            if inserted == 0 {
+                continue;
+            }
+            match numstat.get(&c.hash) {
+                Some(changes) => {
+                    for f in changes {
+                        file_stmt.execute(params![
+                            c.hash,
+                            f.file,
+                            f.additions.map(|v| v as i64),
+                            f.deletions.map(|v| v as i64),
+                        ])?;
+                    }
+                }
+                None => {
+                    for file in &c.files {
+                        file_stmt.execute(params![c.hash, file, Null, Null])?;
+                    }
+                }
+            }
+        }
+    }
+
+    insert_rows(
+        &tx,
+        "file_signals",
+        HOTSPOT_COLUMNS,
+        run_id,
+        report
+            .results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| hotspot_row(i + 1, r)),
+    )?;
+    insert_rows(
+        &tx,
+        "couplings",
+        COUPLING_COLUMNS,
+        run_id,
+        report.couplings.iter().map(coupling_row),
+    )?;
+    insert_rows(
+        &tx,
+        "security_risks",
+        SECURITY_COLUMNS,
+        run_id,
+        report.security_risks.iter().map(security_row),
+    )?;
+    tx.commit()?;
+    Ok(run_id)
+}
+
+fn insert_rows(
+    tx: &Transaction,
+    table: &str,
+    columns: &[(&str, &str)],
+    run_id: i64,
+    rows: impl Iterator<Item = Vec<Value>>,
+) -> rusqlite::Result<()> {
+    let names: Vec<&str> = columns.iter().map(|(name, _)| *name).collect();
+    let marks = vec!["?"; columns.len() + 1].join(", ");
+    let mut stmt = tx.prepare(&format!(
+        "INSERT INTO {table} (run_id, {}) VALUES ({marks})",
+        names.join(", ")
+    ))?;
+    for row in rows {
+        stmt.execute(params_from_iter(
+            std::iter::once(Value::Int(run_id)).chain(row),
+        ))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::log_parser::FileChange;
+    use crate::types::{CouplingEntry, ReportMeta, Treemap, Weights};
+    use std::collections::HashMap;
+
+    fn report() -> Report {
+        Report {
+            meta: ReportMeta {
+                since: "all history".to_string(),
+                commit_count: 2,
+                file_count: 2,
+                analyzed_at: "2024-05-01T00:00:00Z".to_string(),
+                repo_path: "/src/app".to_string(),
+            },
+            weights: Weights::default(),
+            results: Vec::new(),
+            couplings: vec![CouplingEntry {
+                file_a: "a.rs".to_string(),
+                file_b: "b.rs".to_string(),
+                co_changes: 2,
+                strength: 100.0,
+                boundary: None,
+                boundary_allowed: false,
+            }],
+            coupling_clusters: Vec::new(),
+            boundary_violations: Vec::new(),
+            security_risks: Vec::new(),
+            secret_findings: Vec::new(),
+            security_suppressed: 0,
+            auto_excluded: Vec::new(),
+            function_hotspots: Vec::new(),
+            entropy_periods: Vec::new(),
+            knowledge_risks: Vec::new(),
+            treemap: Treemap::default(),
+        }
+    }
+
+    #[test]
+    fn test_runs_accumulate_and_share_commits() {
+        let commits = vec![
+            Commit {
+                hash: "c2".to_string(),
+                author: "ann".to_string(),
+                timestamp: 200,
+                subject: "fix crash".to_string(),
+                files: vec!["a.rs".to_string(), "b.rs".to_string()],
+            },
+            Commit {
+                hash: "c1".to_string(),
+                author: "bo".to_string(),
+                timestamp: 100,
+                subject: "add a".to_string(),
+                files: vec!["a.rs".to_string()],
+            },
+        ];
+        let numstat = HashMap::from([(
+            "c2".to_string(),
+            vec![
+                FileChange {
+                    file: "a.rs".to_string(),
+                    additions: Some(3),
+                    deletions: Some(1),
+                },
+                FileChange {
+                    file: "b.rs".to_string(),
+                    additions: None,
+                    deletions: None,
+                },
+            ],
+        )]);
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        let first = write_run(&mut conn, &report(), "app", &commits, &numstat).ok();
+        let second = write_run(&mut conn, &report(), "app", &commits, &numstat).ok();
+        assert_eq!((first, second), (Some(1), Some(2)));
+
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM commits"), 2, "Stored once");
+        assert_eq!(count("SELECT COUNT(*) FROM commit_files"), 3);
+        assert_eq!(
+            count("SELECT COUNT(*) FROM run_commits WHERE run_id = 2"),
+            2
+        );
+        assert_eq!(count("SELECT COUNT(*) FROM commits WHERE bug_fix = 1"), 1);
+        assert_eq!(
+            count(
+                "SELECT SUM(f.additions) FROM run_commits rc \
+                 JOIN commit_files f USING (hash) WHERE rc.run_id = 1"
+            ),
+            3
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM commit_files WHERE hash = 'c1' AND additions IS NULL"),
+            1,
+            "Commits missing from numstat keep their files"
+        );
+        assert_eq!(count("SELECT COUNT(*) FROM couplings"), 2);
+
+        conn.execute_batch("PRAGMA user_version = 99").unwrap();
+        assert!(matches!(
+            write_run(&mut conn, &report(), "app", &commits, &numstat),
+            Err(RunError::Schema(99))
+        ));
+    }
+}
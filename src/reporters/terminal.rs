@@ -1,4 +1,5 @@
-use crate::types::{HotspotResult, Report, Tier};
+use super::common::{build_recommendations, ref_list};
+use crate::types::{AuthorReport, CouplingQuery, Quadrant, Report, Tier};
 use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color, Table};
 
@@ -28,10 +29,11 @@ pub fn report_terminal(report: &Report) {
         println!();
         for risk in &report.security_risks {
             println!(
-                "   {}  {} [{}] {} (first: {}, last: {})",
+                "   {}  {} [{}/{}] {} (first: {}, last: {})",
                 "⚠".red(),
                 risk.file.cyan(),
                 risk.risk_type.red(),
+                risk.severity.red(),
                 format!(
                     "{} commit{}",
                     risk.commit_count,
@@ -41,6 +43,103 @@ pub fn report_terminal(report: &Report) {
                 risk.first_seen.bright_black(),
                 risk.last_seen.bright_black(),
             );
+            let location = if risk.present_at_head {
+                "still at HEAD".red().bold().to_string()
+            } else if !risk.refs.is_empty() {
+                "removed from HEAD".yellow().to_string()
+            } else {
+                "history only — needs a rewrite to purge"
+                    .yellow()
+                    .to_string()
+            };
+            let refs = if risk.refs.is_empty() {
+                "none".to_string()
+            } else {
+                ref_list(&risk.refs)
+            };
+            println!(
+                "      {} {}",
+                location,
+                format!(
+                    "· refs: {} · added: {} · removed: {} · authors: {}",
+                    refs,
+                    short_hashes(&risk.added_in),
+                    short_hashes(&risk.removed_in),
+                    risk.authors.join(", ")
+                )
+                .bright_black(),
+            );
+        }
+        println!();
+    }
+
+    // ── Secrets in file content ────────────────────────────────────────────
+    if !report.secret_findings.is_empty() {
+        println!(
+            "{}",
+            "🔑 Secrets found in file contents across git history:"
+                .red()
+                .bold()
+        );
+        println!(
+            "{}",
+            "   Values are redacted. Rotate every credential listed, even if removed.".red()
+        );
+        println!();
+        for f in &report.secret_findings {
+            let status = if f.present_at_head {
+                "still at HEAD".red().bold()
+            } else {
+                "removed from HEAD".bright_black()
+            };
+            println!(
+                "   {}  {}:{} [{}] {} {} {}",
+                "⚠".red(),
+                f.file.cyan(),
+                f.line,
+                f.rule.red(),
+                f.redacted,
+                format!("(commit {}, {})", short_hash(&f.commit), f.date).bright_black(),
+                status,
+            );
+        }
+        println!();
+    }
+
+    if report.security_suppressed > 0 {
+        println!(
+            "{}",
+            format!(
+                "   ℹ {} security finding(s) suppressed by the config allowlist",
+                report.security_suppressed
+            )
+            .bright_black()
+        );
+        println!();
+    }
+
+    // ── Auto-excluded generated / vendored files ───────────────────────────
+    if !report.auto_excluded.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "   ℹ {} generated/vendored file(s) excluded from scoring:",
+                report.auto_excluded.len()
+            )
+            .bright_black()
+        );
+        for g in report.auto_excluded.iter().take(10) {
+            println!(
+                "     {} {}",
+                g.file.bright_black(),
+                format!("[{}]", g.reason).bright_black()
+            );
+        }
+        if report.auto_excluded.len() > 10 {
+            println!(
+                "{}",
+                format!("     … and {} more", report.auto_excluded.len() - 10).bright_black()
+            );
         }
         println!();
     }
@@ -82,6 +181,29 @@ pub fn report_terminal(report: &Report) {
 
     println!("{table}");
 
+    // ── Function hotspots ──────────────────────────────────────────────────
+    if !report.function_hotspots.is_empty() {
+        println!();
+        println!("{}", "🔬 Function hotspots:".cyan());
+        let mut ft = Table::new();
+        ft.load_preset(UTF8_FULL);
+        ft.set_header(vec![
+            "RANK", "FUNCTION", "FILE", "SCORE", "COMMITS", "BUGS", "REVERTS",
+        ]);
+        for (i, f) in report.function_hotspots.iter().enumerate() {
+            ft.add_row(vec![
+                Cell::new(format!("{:3}", i + 1)),
+                Cell::new(&f.function).fg(Color::Cyan),
+                Cell::new(truncate_path(&f.file, 36)),
+                score_cell(f.hotspot_score.round() as u64),
+                Cell::new(f.commit_count.to_string()),
+                Cell::new(f.bug_commits.to_string()),
+                Cell::new(f.revert_count.to_string()),
+            ]);
+        }
+        println!("{ft}");
+    }
+
     // ── Co-change coupling ─────────────────────────────────────────────────
     let notable: Vec<_> = report
         .couplings
@@ -107,8 +229,179 @@ pub fn report_terminal(report: &Report) {
         }
     }
 
+    if !report.boundary_violations.is_empty() {
+        println!();
+        println!(
+            "{}",
+            "🚧 Coupling across architectural boundaries:".yellow()
+        );
+        for c in report.boundary_violations.iter().take(5) {
+            println!(
+                "    {} ↔ {} {}",
+                c.file_a.cyan(),
+                c.file_b.cyan(),
+                format!(
+                    "({}, changed together {}x, strength {}%)",
+                    c.boundary.as_deref().unwrap_or_default(),
+                    c.co_changes,
+                    c.strength.round()
+                )
+                .bright_black(),
+            );
+        }
+    }
+
+    if !report.coupling_clusters.is_empty() {
+        println!();
+        println!("{}", "🔗 Files that change together:".yellow());
+        for cluster in report.coupling_clusters.iter().take(5) {
+            let shown: Vec<&str> = cluster.files.iter().take(4).map(String::as_str).collect();
+            let more = cluster.files.len() - shown.len();
+            println!(
+                "    {} {} {}{}",
+                cluster.name.cyan(),
+                format!(
+                    "({} files, cohesion {}%{})",
+                    cluster.files.len(),
+                    cluster.cohesion.round(),
+                    if cluster.cross_component {
+                        ", spans components"
+                    } else {
+                        ""
+                    }
+                )
+                .bright_black(),
+                shown.join(", "),
+                if more > 0 {
+                    format!(" +{more} more")
+                } else {
+                    String::new()
+                },
+            );
+        }
+    }
+
+    // ── Churn × complexity ─────────────────────────────────────────────────
+    let quadrant: Vec<_> = report
+        .results
+        .iter()
+        .filter(|r| r.quadrant == Quadrant::Hotspot)
+        .take(5)
+        .collect();
+    if !quadrant.is_empty() {
+        println!();
+        println!(
+            "{}",
+            "📐 High churn × high complexity — refactor candidates:".yellow()
+        );
+        for r in &quadrant {
+            let longest = if r.details.longest_function > 0 {
+                format!(", longest function {} lines", r.details.longest_function)
+            } else {
+                String::new()
+            };
+            println!(
+                "    {} {}",
+                r.file.cyan(),
+                format!(
+                    "({} LOC, complexity {}{})",
+                    r.details.loc,
+                    r.complexity_score.round(),
+                    longest
+                )
+                .bright_black(),
+            );
+        }
+    }
+
+    // ── Test co-evolution ──────────────────────────────────────────────────
+    let untested: Vec<_> = report
+        .results
+        .iter()
+        .filter(|r| {
+            r.details
+                .tests
+                .as_ref()
+                .is_some_and(|t| t.test_files.is_empty() && !t.inline_tests)
+        })
+        .take(5)
+        .collect();
+    if !untested.is_empty() {
+        println!();
+        println!("{}", "🧪 Hotspots with no test file:".yellow());
+        for r in &untested {
+            println!(
+                "    {} {}",
+                r.file.cyan(),
+                format!(
+                    "({} commits, score {})",
+                    r.details.commit_count,
+                    r.hotspot_score.round()
+                )
+                .bright_black(),
+            );
+        }
+    }
+
+    // ── Orphaned knowledge ─────────────────────────────────────────────────
+    let orphaned: Vec<_> = report
+        .results
+        .iter()
+        .filter_map(|r| Some((r, r.details.ownership.as_ref().filter(|o| o.orphaned)?)))
+        .take(5)
+        .collect();
+    if !orphaned.is_empty() {
+        println!();
+        println!(
+            "{}",
+            "👻 Orphaned hotspots — primary authors no longer active:".yellow()
+        );
+        for (r, o) in &orphaned {
+            println!(
+                "    {} {}",
+                r.file.cyan(),
+                format!(
+                    "({}, last active {} days ago)",
+                    o.primary_authors.join(", "),
+                    o.primary_last_active_days
+                )
+                .bright_black(),
+            );
+        }
+    }
+    if !report.knowledge_risks.is_empty() {
+        println!();
+        println!("{}", "👤 Files at risk if one author leaves:".yellow());
+        for risk in report.knowledge_risks.iter().take(5) {
+            let shown: Vec<&str> = risk.files.iter().take(3).map(String::as_str).collect();
+            let more = risk.files.len() - shown.len();
+            println!(
+                "    {} {} {}{}",
+                risk.author.cyan(),
+                format!(
+                    "({} files{})",
+                    risk.files.len(),
+                    if risk.inactive { ", inactive" } else { "" }
+                )
+                .bright_black(),
+                shown.join(", "),
+                if more > 0 {
+                    format!(" +{more} more")
+                } else {
+                    String::new()
+                },
+            );
+        }
+    }
+
     // ── Recommendations ────────────────────────────────────────────────────
-    let recs = build_recommendations(&report.results);
+    let recs = build_recommendations(&report.results, |name, urgent| {
+        if urgent {
+            name.red().to_string()
+        } else {
+            name.yellow().to_string()
+        }
+    });
     if !recs.is_empty() {
         println!();
         println!("{}", "💡 Recommendations:".cyan());
@@ -120,6 +413,131 @@ pub fn report_terminal(report: &Report) {
     println!();
 }
 
+/// Prints the `coupled` query: files that usually change with one file.
+pub fn report_coupled_terminal(report: &CouplingQuery) {
+    eprintln!();
+    println!(
+        "{} — files that change with {} since \"{}\" ({} change sets{})",
+        "🔥 git-scanline".red().bold(),
+        report.file.cyan(),
+        report.meta.since.bright_black(),
+        report.change_sets.to_string().bright_black(),
+        report
+            .window_hours
+            .map(|h| format!(", same-author commits within {h}h merged"))
+            .unwrap_or_default()
+            .bright_black(),
+    );
+    println!();
+
+    if report.coupled.is_empty() {
+        println!(
+            "{}",
+            "  No file changed together with it often enough.".green()
+        );
+        println!();
+        return;
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["FILE", "CONFIDENCE", "SUPPORT", "REVERSE", "STRENGTH"]);
+    for c in &report.coupled {
+        let confidence = format!("{}%", c.confidence.round());
+        let confidence = if c.confidence >= 50.0 {
+            Cell::new(confidence)
+                .fg(Color::Yellow)
+                .add_attribute(Attribute::Bold)
+        } else {
+            Cell::new(confidence)
+        };
+        table.add_row(vec![
+            Cell::new(&c.file),
+            confidence,
+            Cell::new(c.support.to_string()),
+            Cell::new(format!("{}%", c.reverse_confidence.round())),
+            Cell::new(format!("{}%", c.strength.round())),
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "{}",
+        "  CONFIDENCE: share of its changes that also touched the file. \
+         SUPPORT: changes together. REVERSE: the other way round."
+            .bright_black()
+    );
+    println!();
+}
+
+/// Prints the `authors` report: one row per author or team.
+pub fn report_authors_terminal(report: &AuthorReport) {
+    let by_team = report.group_by == "team";
+    eprintln!();
+    println!(
+        "{} — {} since \"{}\" ({} commits, {} files)",
+        "🔥 git-scanline".red().bold(),
+        if by_team { "teams" } else { "authors" },
+        report.meta.since.bright_black(),
+        report.meta.commit_count.to_string().bright_black(),
+        report.meta.file_count.to_string().bright_black(),
+    );
+    println!();
+
+    if report.authors.is_empty() {
+        println!("{}", "  No commits found with current filters.".yellow());
+        println!();
+        return;
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        if by_team { "TEAM" } else { "AUTHOR" },
+        "COMMITS",
+        "FILES",
+        "HOTSPOTS",
+        "BUG FIXES",
+        "REVERTS",
+        "WIP",
+        "AVG SIZE",
+    ]);
+    for a in &report.authors {
+        let wip = format!("{}%", (a.wip_ratio * 100.0).round());
+        let wip_cell = if a.wip_ratio >= 0.25 {
+            Cell::new(wip).fg(Color::Yellow)
+        } else {
+            Cell::new(wip)
+        };
+        let hotspots = if a.hotspots_owned > 0 {
+            Cell::new(a.hotspots_owned.to_string()).add_attribute(Attribute::Bold)
+        } else {
+            Cell::new("0").fg(Color::DarkGrey)
+        };
+        let name = if by_team {
+            format!("{} ({})", a.name, a.members.len())
+        } else {
+            a.name.clone()
+        };
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(a.commits.to_string()),
+            Cell::new(a.files_touched.to_string()),
+            hotspots,
+            Cell::new(a.bug_fix_commits.to_string()),
+            Cell::new(a.reverts_authored.to_string()),
+            wip_cell,
+            Cell::new(format!("{:.1}", a.avg_commit_size)),
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "{}",
+        "  HOTSPOTS: reported hotspots whose top author this is. AVG SIZE: files per commit."
+            .bright_black()
+    );
+    println!();
+}
+
 // ─── Cell builders ────────────────────────────────────────────────────────────
 
 /// Score cell: plain numeric text + color chosen by tier.
@@ -162,55 +580,24 @@ fn tier_cell(tier: &Tier) -> Cell {
 
 // ─── Other helpers ────────────────────────────────────────────────────────────
 
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
+fn short_hashes(hashes: &[String]) -> String {
+    if hashes.is_empty() {
+        return "—".to_string();
+    }
+    hashes
+        .iter()
+        .map(|h| short_hash(h))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn truncate_path(s: &str, max: usize) -> String {
     if s.len() <= max {
         return s.to_string();
     }
     format!("…{}", &s[s.len().saturating_sub(max - 1)..])
 }
-
-fn build_recommendations(results: &[HotspotResult]) -> Vec<String> {
-    let mut recs = Vec::new();
-    for r in results.iter().take(10) {
-        let name = r.file.split('/').next_back().unwrap_or(&r.file);
-        if r.details.top_author_percent >= 80.0 && r.details.author_count <= 2 {
-            recs.push(format!(
-                "{} has {}% single-author commits — consider a knowledge-transfer session",
-                name.yellow(),
-                r.details.top_author_percent.round()
-            ));
-        }
-        if r.details.burst_incidents >= 3 {
-            recs.push(format!(
-                "{} shows burst patterns: {} rapid-commit windows detected",
-                name.yellow(),
-                r.details.burst_incidents
-            ));
-        }
-        if r.details.revert_count >= 2 {
-            recs.push(format!(
-                "{} has been reverted {} times — consider adding tests or stricter review",
-                name.yellow(),
-                r.details.revert_count
-            ));
-        }
-        if r.details.wip_commits >= 3 {
-            recs.push(format!(
-                "{} appears in {} WIP/low-quality commits — this area needs careful review",
-                name.yellow(),
-                r.details.wip_commits
-            ));
-        }
-        if r.details.large_commit_count >= 3 {
-            recs.push(format!(
-                "{} was swept up in {} large commits — consider smaller, focused PRs",
-                name.yellow(),
-                r.details.large_commit_count
-            ));
-        }
-        if recs.len() >= 8 {
-            break;
-        }
-    }
-    recs
-}
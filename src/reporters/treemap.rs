@@ -0,0 +1,389 @@
+use super::common::esc;
+use crate::types::{Treemap, TreemapFile};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+const WIDTH: f64 = 1200.0;
+const MAP_HEIGHT: f64 = 680.0;
+const LEGEND_HEIGHT: f64 = 36.0;
+/// Height of a directory's name strip.
+const HEADER: f64 = 14.0;
+/// Gap between a directory's frame and its children.
+const PAD: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+#[derive(Default)]
+struct Dir<'a> {
+    dirs: BTreeMap<&'a str, Dir<'a>>,
+    files: Vec<(&'a str, &'a TreemapFile)>,
+    size: f64,
+    /// Highest file score inside, for directories too small to open.
+    max_score: f64,
+}
+
+enum Item<'a> {
+    Dir(String, &'a Dir<'a>),
+    File(&'a str, &'a TreemapFile),
+}
+
+/// Writes the treemap as a standalone SVG image, to a file if given,
+/// otherwise stdout.
+pub fn report_treemap(
+    treemap: &Treemap,
+    repo: &str,
+    output_file: Option<&Path>,
+) -> Result<(), String> {
+    let svg = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}\n",
+        treemap_svg(treemap, Some(repo))
+    );
+    if let Some(path) = output_file {
+        fs::write(path, &svg).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        eprintln!("✓ Treemap SVG written to {}", path.display());
+    } else {
+        std::io::stdout()
+            .lock()
+            .write_all(svg.as_bytes())
+            .map_err(|e| format!("Failed to write stdout: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Code-city view: one rectangle per file, area by `treemap.size_by`, color
+/// by hotspot score, nested by directory. A title line is added for the
+/// standalone image; the HTML report has its own card heading.
+pub fn treemap_svg(treemap: &Treemap, title: Option<&str>) -> String {
+    let top = if title.is_some() { 28.0 } else { 0.0 };
+    let height = top + MAP_HEIGHT + LEGEND_HEIGHT;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {WIDTH} {height}\" \
+         width=\"{WIDTH}\" height=\"{height}\" font-family=\"Inter,system-ui,sans-serif\" \
+         role=\"img\" aria-label=\"Hotspot treemap\">"
+    );
+    if let Some(repo) = title {
+        out.push_str(&format!(
+            "<rect width=\"{WIDTH}\" height=\"{height}\" fill=\"#0f172a\"/>\
+             <text x=\"4\" y=\"18\" font-size=\"14\" font-weight=\"600\" fill=\"#e2e8f0\">\
+             Hotspot treemap — {} — area: {}</text>",
+            esc(repo),
+            treemap.size_by.label()
+        ));
+    }
+
+    let root = build_tree(&treemap.files);
+    let area = Rect {
+        x: 0.0,
+        y: top,
+        w: WIDTH,
+        h: MAP_HEIGHT,
+    };
+    if root.size > 0.0 {
+        layout_dir(&root, "", area, treemap.size_by.label(), &mut out);
+    } else {
+        out.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"13\" fill=\"#64748b\">\
+             No scored files with {}</text>",
+            WIDTH / 2.0,
+            top + MAP_HEIGHT / 2.0,
+            treemap.size_by.label()
+        ));
+    }
+    legend(top + MAP_HEIGHT + 12.0, &mut out);
+    out.push_str("</svg>");
+    out
+}
+
+fn build_tree(files: &[TreemapFile]) -> Dir<'_> {
+    let mut root = Dir::default();
+    for f in files.iter().filter(|f| f.size > 0) {
+        let mut parts: Vec<&str> = f.file.split('/').collect();
+        let name = parts.pop().unwrap_or_default();
+        let mut dir = &mut root;
+        dir.size += f.size as f64;
+        dir.max_score = dir.max_score.max(f.score);
+        for part in parts {
+            dir = dir.dirs.entry(part).or_default();
+            dir.size += f.size as f64;
+            dir.max_score = dir.max_score.max(f.score);
+        }
+        dir.files.push((name, f));
+    }
+    root
+}
+
+fn layout_dir(dir: &Dir, path: &str, rect: Rect, unit: &str, out: &mut String) {
+    let mut items: Vec<(f64, Item)> = dir
+        .dirs
+        .iter()
+        .map(|(name, sub)| {
+            // Collapse chains like src/ → main/ → java/ into one frame.
+            let mut name = name.to_string();
+            let mut sub = sub;
+            while sub.files.is_empty() && sub.dirs.len() == 1 {
+                let (next, child) = sub.dirs.iter().next().expect("one subdirectory");
+                name = format!("{name}/{next}");
+                sub = child;
+            }
+            (sub.size, Item::Dir(name, sub))
+        })
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, f)| (f.size as f64, Item::File(name, f))),
+        )
+        .collect();
+    items.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    let sizes: Vec<f64> = items.iter().map(|(s, _)| *s).collect();
+
+    for ((_, item), r) in items.iter().zip(squarify(&sizes, rect)) {
+        if r.w < 0.5 || r.h < 0.5 {
+            continue;
+        }
+        match item {
+            Item::File(name, f) => {
+                out.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" \
+                     stroke=\"#0f172a\" stroke-width=\"0.5\"><title>{} — score {} — {} {unit}</title></rect>",
+                    r.x,
+                    r.y,
+                    r.w,
+                    r.h,
+                    heat(f.score),
+                    esc(&f.file),
+                    f.score.round(),
+                    f.size
+                ));
+                label(
+                    name,
+                    r.x + 3.0,
+                    r.y + 12.0,
+                    r.w - 6.0,
+                    r.h >= 16.0,
+                    10.0,
+                    out,
+                );
+            }
+            Item::Dir(name, sub) => {
+                let full = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}/{name}")
+                };
+                let open = r.w >= 3.0 * PAD + 8.0 && r.h >= HEADER + 2.0 * PAD + 8.0;
+                if !open {
+                    out.push_str(&format!(
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" \
+                         stroke=\"#0f172a\" stroke-width=\"0.5\"><title>{}/ — max score {} — {} {unit}</title></rect>",
+                        r.x, r.y, r.w, r.h,
+                        heat(sub.max_score),
+                        esc(&full),
+                        sub.max_score.round(),
+                        sub.size
+                    ));
+                    continue;
+                }
+                out.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#1e293b\" \
+                     stroke=\"#475569\" stroke-width=\"1\"><title>{}/ — {} {unit}</title></rect>",
+                    r.x, r.y, r.w, r.h,
+                    esc(&full),
+                    sub.size
+                ));
+                label(
+                    &format!("{name}/"),
+                    r.x + 3.0,
+                    r.y + 11.0,
+                    r.w - 6.0,
+                    true,
+                    10.0,
+                    out,
+                );
+                let inner = Rect {
+                    x: r.x + PAD,
+                    y: r.y + HEADER,
+                    w: r.w - 2.0 * PAD,
+                    h: r.h - HEADER - PAD,
+                };
+                layout_dir(sub, &full, inner, unit, out);
+            }
+        }
+    }
+}
+
+/// Squarified treemap (Bruls, Huizing & van Wijk): `sizes` sorted largest
+/// first are laid out in rows along the shorter side of the remaining space,
+/// growing each row while that keeps its rectangles closer to square.
+fn squarify(sizes: &[f64], rect: Rect) -> Vec<Rect> {
+    let total: f64 = sizes.iter().sum();
+    if total <= 0.0 || rect.w <= 0.0 || rect.h <= 0.0 {
+        return vec![
+            Rect {
+                w: 0.0,
+                h: 0.0,
+                ..rect
+            };
+            sizes.len()
+        ];
+    }
+    let scale = rect.w * rect.h / total;
+    let areas: Vec<f64> = sizes.iter().map(|s| s * scale).collect();
+
+    let worst = |row: &[f64], side: f64| -> f64 {
+        let sum: f64 = row.iter().sum();
+        let max = row.iter().cloned().fold(f64::MIN, f64::max);
+        let min = row.iter().cloned().fold(f64::MAX, f64::min);
+        (side * side * max / (sum * sum)).max(sum * sum / (side * side * min))
+    };
+
+    let mut out = Vec::with_capacity(areas.len());
+    let mut free = rect;
+    let mut start = 0;
+    while start < areas.len() {
+        let side = free.w.min(free.h);
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst(&areas[start..=end], side) <= worst(&areas[start..end], side)
+        {
+            end += 1;
+        }
+        let row = &areas[start..end];
+        let sum: f64 = row.iter().sum();
+        if free.w >= free.h {
+            // Column on the left edge.
+            let thick = if free.h > 0.0 { sum / free.h } else { 0.0 };
+            let mut y = free.y;
+            for a in row {
+                let h = if thick > 0.0 { a / thick } else { 0.0 };
+                out.push(Rect {
+                    x: free.x,
+                    y,
+                    w: thick,
+                    h,
+                });
+                y += h;
+            }
+            free.x += thick;
+            free.w = (free.w - thick).max(0.0);
+        } else {
+            // Row along the top edge.
+            let thick = if free.w > 0.0 { sum / free.w } else { 0.0 };
+            let mut x = free.x;
+            for a in row {
+                let w = if thick > 0.0 { a / thick } else { 0.0 };
+                out.push(Rect {
+                    x,
+                    y: free.y,
+                    w,
+                    h: thick,
+                });
+                x += w;
+            }
+            free.y += thick;
+            free.h = (free.h - thick).max(0.0);
+        }
+        start = end;
+    }
+    out
+}
+
+/// Text clipped to `width` by dropping characters; omitted when nothing fits.
+fn label(text: &str, x: f64, y: f64, width: f64, fits: bool, size: f64, out: &mut String) {
+    let max_chars = (width / (size * 0.6)) as usize;
+    if !fits || max_chars < 3 {
+        return;
+    }
+    let count = text.chars().count();
+    let shown = if count > max_chars {
+        let kept: String = text.chars().take(max_chars - 1).collect();
+        format!("{kept}…")
+    } else {
+        text.to_string()
+    };
+    out.push_str(&format!(
+        "<text x=\"{x:.1}\" y=\"{y:.1}\" font-size=\"{size}\" fill=\"#f8fafc\" \
+         pointer-events=\"none\">{}</text>",
+        esc(&shown)
+    ));
+}
+
+fn legend(y: f64, out: &mut String) {
+    out.push_str(&format!(
+        "<defs><linearGradient id=\"treemap-heat\">\
+         <stop offset=\"0\" stop-color=\"{}\"/><stop offset=\"0.5\" stop-color=\"{}\"/>\
+         <stop offset=\"1\" stop-color=\"{}\"/></linearGradient></defs>\
+         <text x=\"4\" y=\"{:.1}\" font-size=\"11\" fill=\"#64748b\">Hotspot score</text>\
+         <text x=\"96\" y=\"{:.1}\" font-size=\"11\" fill=\"#64748b\">0</text>\
+         <rect x=\"110\" y=\"{y:.1}\" width=\"200\" height=\"10\" rx=\"2\" fill=\"url(#treemap-heat)\"/>\
+         <text x=\"316\" y=\"{:.1}\" font-size=\"11\" fill=\"#64748b\">100</text>",
+        heat(0.0),
+        heat(50.0),
+        heat(100.0),
+        y + 9.0,
+        y + 9.0,
+        y + 9.0
+    ));
+}
+
+/// Green at 0 through yellow to red at 100.
+fn heat(score: f64) -> String {
+    let hue = 120.0 * (1.0 - score.clamp(0.0, 100.0) / 100.0);
+    format!("hsl({hue:.0},70%,42%)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TreemapSize;
+
+    #[test]
+    fn test_squarify_fills_rect_proportionally() {
+        let rect = Rect {
+            x: 10.0,
+            y: 20.0,
+            w: 600.0,
+            h: 400.0,
+        };
+        let sizes = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let rects = squarify(&sizes, rect);
+        let total: f64 = sizes.iter().sum();
+        for (s, r) in sizes.iter().zip(&rects) {
+            assert!((r.w * r.h - s / total * 240_000.0).abs() < 1e-6);
+            assert!(r.x >= 10.0 - 1e-9 && r.x + r.w <= 610.0 + 1e-6);
+            assert!(r.y >= 20.0 - 1e-9 && r.y + r.h <= 420.0 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_treemap_nests_and_collapses_directories() {
+        let file = |f: &str, size, score| TreemapFile {
+            file: f.to_string(),
+            size,
+            score,
+        };
+        let treemap = Treemap {
+            size_by: TreemapSize::Churn,
+            files: vec![
+                file("src/main/java/App.java", 400, 90.0),
+                file("src/main/java/Util.java", 200, 10.0),
+                file("README.md", 100, 5.0),
+                file("empty.txt", 0, 0.0),
+            ],
+        };
+        let svg = treemap_svg(&treemap, None);
+        assert!(svg.contains(">src/main/java/</text>"), "Chain collapsed");
+        assert!(
+            svg.contains("<title>src/main/java/App.java — score 90 — 400 lines changed</title>")
+        );
+        assert!(svg.contains(&format!("fill=\"{}\"", heat(90.0))));
+        assert!(!svg.contains("empty.txt"), "Zero-size files are skipped");
+    }
+}
@@ -6,7 +6,13 @@ const TIER_CRITICAL: f64 = 75.0;
 const TIER_HIGH: f64 = 50.0;
 const TIER_MEDIUM: f64 = 25.0;
 
+/// Score multiplier for files untouched for the configured stable period.
+const STABLE_DAMPENING: f64 = 0.5;
+
 /// Aggregates all analyzer outputs into a final Hotspot Score (0–100) per file.
+///
+/// With `dampen_after_days`, files not modified for that many days have their
+/// score halved: old, settled code is rarely where the next bug comes from.
 #[allow(clippy::too_many_arguments)]
 pub fn score_hotspots(
     files: &[String],
@@ -17,12 +23,19 @@ pub fn score_hotspots(
     coupling_data: &[CouplingEntry],
     silo_data: &HashMap<String, SiloData>,
     commit_quality_data: &HashMap<String, CommitQualityData>,
+    complexity_data: &HashMap<String, ComplexityData>,
+    test_data: &HashMap<String, TestCoevolution>,
+    coverage_data: &HashMap<String, CoverageData>,
+    entropy_data: &HashMap<String, EntropyData>,
+    age_data: &HashMap<String, CodeAgeData>,
+    experience_data: &HashMap<String, ExperienceData>,
+    dampen_after_days: Option<i64>,
     diff_stats: &DiffStatsMap,
     weights: &Weights,
 ) -> Vec<HotspotResult> {
     let coupling_scores = get_coupling_scores(files, coupling_data);
 
-    files
+    let mut results: Vec<HotspotResult> = files
         .iter()
         .map(|file| {
             let churn = churn_data.get(file);
@@ -31,6 +44,11 @@ pub fn score_hotspots(
             let bursts = burst_data.get(file);
             let silo = silo_data.get(file);
             let cq = commit_quality_data.get(file);
+            let cx = complexity_data.get(file);
+            let tests = test_data.get(file);
+            let age = age_data.get(file);
+            let experience = experience_data.get(file);
+            let line_coverage = coverage_data.get(file).map(|d| d.line_coverage);
             let diff = diff_stats.get(file);
 
             let churn_score = churn.map_or(0.0, |d| d.weighted_score);
@@ -40,14 +58,33 @@ pub fn score_hotspots(
             let coupling_score = *coupling_scores.get(file).unwrap_or(&0.0);
             let silo_score = silo.map_or(0.0, |d| d.top_author_percent);
             let commit_quality_score = cq.map_or(0.0, |d| d.commit_quality_score);
+            let complexity_score = cx.map_or(0.0, |d| d.complexity_score);
+            let test_gap_score = tests.map_or(0.0, |d| d.test_gap_score);
+            let coverage_gap_score = line_coverage.map_or(0.0, |c| 100.0 - c);
+            let entropy_score = entropy_data.get(file).map_or(0.0, |d| d.entropy_score);
+            let experience_score = experience.map_or(0.0, |d| d.experience_score);
 
-            let hotspot_score = churn_score * weights.churn
+            let raw_score = churn_score * weights.churn
                 + bug_fix_score * weights.bugs
                 + revert_score * weights.reverts
                 + burst_score * weights.bursts
                 + coupling_score * weights.coupling
                 + silo_score * weights.silo
-                + commit_quality_score * weights.commit_quality;
+                + commit_quality_score * weights.commit_quality
+                + complexity_score * weights.complexity
+                + test_gap_score * weights.tests
+                + coverage_gap_score * weights.coverage
+                + entropy_score * weights.entropy
+                + experience_score * weights.experience;
+            let stability_dampened = matches!(
+                (age, dampen_after_days),
+                (Some(a), Some(days)) if a.last_modified_days >= days
+            );
+            let hotspot_score = if stability_dampened {
+                raw_score * STABLE_DAMPENING
+            } else {
+                raw_score
+            };
 
             HotspotResult {
                 file: file.clone(),
@@ -59,6 +96,15 @@ pub fn score_hotspots(
                 coupling_score,
                 silo_score,
                 commit_quality_score,
+                complexity_score,
+                test_gap_score,
+                line_coverage,
+                coverage_gap_score,
+                entropy_score,
+                experience_score,
+                stability_dampened,
+                // Assigned below once the medians are known.
+                quadrant: Quadrant::Healthy,
                 tier: get_tier(hotspot_score),
                 details: HotspotDetails {
                     commit_count: churn.map_or(0, |d| d.commit_count),
@@ -73,10 +119,51 @@ pub fn score_hotspots(
                     author_count: silo.map_or(1, |d| d.author_count),
                     additions: diff.map_or(0, |d| d.additions),
                     deletions: diff.map_or(0, |d| d.deletions),
+                    loc: cx.map_or(0, |d| d.loc),
+                    max_indent: cx.map_or(0, |d| d.max_indent),
+                    longest_function: cx.map_or(0, |d| d.longest_function),
+                    tests: tests.cloned(),
+                    age: age.cloned(),
+                    experience: experience.cloned(),
+                    // Filled in by the caller for reported results.
+                    ownership: None,
+                    history: None,
+                    coupled: Vec::new(),
                 },
             }
         })
-        .collect()
+        .collect();
+
+    let churn_median = median(results.iter().map(|r| r.churn_score).collect());
+    let complexity_median = median(results.iter().map(|r| r.complexity_score).collect());
+    for r in &mut results {
+        r.quadrant = get_quadrant(
+            r.churn_score,
+            r.complexity_score,
+            churn_median,
+            complexity_median,
+        );
+    }
+    results
+}
+
+fn get_quadrant(churn: f64, complexity: f64, churn_median: f64, cx_median: f64) -> Quadrant {
+    let high_churn = churn > 0.0 && churn >= churn_median;
+    let high_cx = complexity > 0.0 && complexity >= cx_median;
+    match (high_churn, high_cx) {
+        (true, true) => Quadrant::Hotspot,
+        (true, false) => Quadrant::ActiveSimple,
+        (false, true) => Quadrant::StableComplex,
+        (false, false) => Quadrant::Healthy,
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values[values.len() / 2]
 }
 
 fn get_tier(score: f64) -> Tier {
@@ -200,6 +287,13 @@ mod tests {
             &[],
             &zero_silo(&files),
             &zero_quality(&files),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
             &DiffStatsMap::new(),
             &Weights::default(),
         );
@@ -237,6 +331,13 @@ mod tests {
             &[],
             &zero_silo(&files),
             &zero_quality(&files),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
             &DiffStatsMap::new(),
             &Weights::default(),
         );
@@ -260,6 +361,13 @@ mod tests {
             &[],
             &zero_silo(&files),
             &zero_quality(&files),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
             &DiffStatsMap::new(),
             &Weights::default(),
         );
@@ -294,6 +402,13 @@ mod tests {
             &[],
             &silo,
             &quality,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
             &DiffStatsMap::new(),
             &Weights::default(),
         );
@@ -303,4 +418,210 @@ mod tests {
         );
         assert_eq!(results[0].tier, Tier::Critical);
     }
+
+    #[test]
+    fn test_quadrant_split_at_median() {
+        assert_eq!(get_quadrant(80.0, 70.0, 50.0, 50.0), Quadrant::Hotspot);
+        assert_eq!(get_quadrant(80.0, 10.0, 50.0, 50.0), Quadrant::ActiveSimple);
+        assert_eq!(
+            get_quadrant(10.0, 70.0, 50.0, 50.0),
+            Quadrant::StableComplex
+        );
+        assert_eq!(get_quadrant(10.0, 10.0, 50.0, 50.0), Quadrant::Healthy);
+        assert_eq!(
+            get_quadrant(0.0, 0.0, 0.0, 0.0),
+            Quadrant::Healthy,
+            "Zero signals are never high, even when the median is zero"
+        );
+        assert_eq!(median(vec![3.0, 1.0, 2.0]), 2.0);
+    }
+
+    #[test]
+    fn test_complexity_weight_applied() {
+        let files = vec!["a.rs".to_string()];
+        let complexity = HashMap::from([(
+            "a.rs".to_string(),
+            ComplexityData {
+                loc: 500,
+                total_indent: 900,
+                max_indent: 6,
+                longest_function: 120,
+                complexity_score: 100.0,
+            },
+        )]);
+        let results = |weights: &Weights| {
+            score_hotspots(
+                &files,
+                &zero_churn(&files),
+                &zero_bugs(&files),
+                &zero_reverts(&files),
+                &zero_bursts(&files),
+                &[],
+                &zero_silo(&files),
+                &zero_quality(&files),
+                &complexity,
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                None,
+                &DiffStatsMap::new(),
+                weights,
+            )
+        };
+        let unweighted = results(&Weights::default());
+        assert_eq!(
+            unweighted[0].hotspot_score, 0.0,
+            "Complexity has no weight by default"
+        );
+        assert_eq!(unweighted[0].details.loc, 500);
+        let weighted = Weights {
+            complexity: 0.5,
+            ..Weights::default()
+        };
+        assert_eq!(
+            results(&weighted)[0].hotspot_score,
+            50.0,
+            "Complexity should contribute score × weight"
+        );
+    }
+
+    #[test]
+    fn test_test_gap_ignored_unless_weighted() {
+        let files = vec!["a.rs".to_string()];
+        let tests = HashMap::from([(
+            "a.rs".to_string(),
+            TestCoevolution {
+                test_files: Vec::new(),
+                inline_tests: false,
+                co_changed_commits: 0,
+                co_change_ratio: None,
+                test_gap_score: 100.0,
+            },
+        )]);
+        let score = |weights: &Weights| {
+            score_hotspots(
+                &files,
+                &zero_churn(&files),
+                &zero_bugs(&files),
+                &zero_reverts(&files),
+                &zero_bursts(&files),
+                &[],
+                &zero_silo(&files),
+                &zero_quality(&files),
+                &HashMap::new(),
+                &tests,
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                None,
+                &DiffStatsMap::new(),
+                weights,
+            )[0]
+            .hotspot_score
+        };
+        assert_eq!(
+            score(&Weights::default()),
+            0.0,
+            "Test gap has no weight by default"
+        );
+        let weighted = Weights {
+            tests: 0.5,
+            ..Weights::default()
+        };
+        assert!((score(&weighted) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_coverage_gap_scored_only_when_known() {
+        let files = vec!["covered.rs".to_string(), "unknown.rs".to_string()];
+        let coverage = HashMap::from([(
+            "covered.rs".to_string(),
+            CoverageData {
+                lines_covered: 30,
+                lines_total: 100,
+                line_coverage: 30.0,
+            },
+        )]);
+        let results = score_hotspots(
+            &files,
+            &zero_churn(&files),
+            &zero_bugs(&files),
+            &zero_reverts(&files),
+            &zero_bursts(&files),
+            &[],
+            &zero_silo(&files),
+            &zero_quality(&files),
+            &HashMap::new(),
+            &HashMap::new(),
+            &coverage,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            None,
+            &DiffStatsMap::new(),
+            &Weights::default(),
+        );
+        assert_eq!(results[0].line_coverage, Some(30.0));
+        assert!((results[0].coverage_gap_score - 70.0).abs() < 1e-9);
+        assert!(
+            (results[0].hotspot_score - 70.0 * Weights::default().coverage).abs() < 1e-9,
+            "Coverage gap should contribute gap × weight"
+        );
+        assert_eq!(results[1].line_coverage, None);
+        assert_eq!(
+            results[1].hotspot_score, 0.0,
+            "Unknown coverage is not a gap"
+        );
+    }
+
+    #[test]
+    fn test_stable_files_dampened_only_when_configured() {
+        let files = vec!["old.rs".to_string(), "fresh.rs".to_string()];
+        let mut churn = zero_churn(&files);
+        churn.get_mut("old.rs").unwrap().weighted_score = 100.0;
+        churn.get_mut("fresh.rs").unwrap().weighted_score = 100.0;
+        let age = |days| CodeAgeData {
+            last_modified_days: days,
+            first_seen_days: 1000,
+            median_line_age_days: None,
+            age_class: AgeClass::Active,
+        };
+        let ages = HashMap::from([
+            ("old.rs".to_string(), age(400)),
+            ("fresh.rs".to_string(), age(5)),
+        ]);
+        let score = |dampen| {
+            score_hotspots(
+                &files,
+                &churn,
+                &zero_bugs(&files),
+                &zero_reverts(&files),
+                &zero_bursts(&files),
+                &[],
+                &zero_silo(&files),
+                &zero_quality(&files),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &ages,
+                &HashMap::new(),
+                dampen,
+                &DiffStatsMap::new(),
+                &Weights::default(),
+            )
+        };
+        let plain = score(None);
+        assert_eq!(plain[0].hotspot_score, plain[1].hotspot_score);
+        let dampened = score(Some(365));
+        assert!(dampened[0].stability_dampened);
+        assert!(
+            (dampened[0].hotspot_score - plain[0].hotspot_score * STABLE_DAMPENING).abs() < 1e-9,
+            "Untouched for over a year: score halved"
+        );
+        assert!(!dampened[1].stability_dampened);
+    }
 }
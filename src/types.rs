@@ -30,6 +30,111 @@ pub struct ChurnData {
     pub raw_score: f64,
 }
 
+/// Size and shape of a file at HEAD.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComplexityData {
+    /// Non-blank, non-comment lines.
+    pub loc: usize,
+    /// Sum of indentation levels over all code lines.
+    pub total_indent: usize,
+    pub max_indent: usize,
+    /// Lines in the longest function-like block.
+    pub longest_function: usize,
+    pub complexity_score: f64,
+}
+
+/// Where a file sits in its lifecycle, judged by its change history.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeClass {
+    /// First changed recently.
+    New,
+    /// Changed within the stable period.
+    Active,
+    /// Untouched for the stable period.
+    Stable,
+    /// Untouched for twice the stable period.
+    Abandoned,
+}
+
+impl std::fmt::Display for AgeClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgeClass::New => write!(f, "new"),
+            AgeClass::Active => write!(f, "active"),
+            AgeClass::Stable => write!(f, "stable"),
+            AgeClass::Abandoned => write!(f, "abandoned"),
+        }
+    }
+}
+
+/// Age of a file's history and of its lines at HEAD.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeAgeData {
+    pub last_modified_days: i64,
+    pub first_seen_days: i64,
+    /// Median age of the file's lines at HEAD from `git blame`; computed for
+    /// reported results only.
+    pub median_line_age_days: Option<i64>,
+    pub age_class: AgeClass,
+}
+
+/// Line coverage of a file from an external coverage report.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageData {
+    pub lines_covered: usize,
+    pub lines_total: usize,
+    /// Percentage, 0–100.
+    pub line_coverage: f64,
+}
+
+/// How a source file's tests change alongside it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestCoevolution {
+    /// Test files at HEAD paired by naming convention or config rule.
+    pub test_files: Vec<String>,
+    /// The file contains its own tests (Rust `#[cfg(test)]`).
+    pub inline_tests: bool,
+    /// Commits touching the file that also touched one of `test_files`.
+    pub co_changed_commits: usize,
+    /// Share of the file's commits that also touched its tests; `None`
+    /// without paired test files or commits.
+    pub co_change_ratio: Option<f64>,
+    /// 100 with no tests at all, else the share of commits that left tests untouched.
+    pub test_gap_score: f64,
+}
+
+/// A file's share of change entropy across all periods.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntropyData {
+    /// Sum over periods of the file's change share × period entropy.
+    pub change_entropy: f64,
+    pub entropy_score: f64,
+}
+
+/// How experienced the authors changing a file were at the time.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperienceData {
+    /// Changes by repository newcomers or by authors new to an existing file.
+    pub low_experience_changes: usize,
+    /// Mean of the author's earlier commits to the file, per change.
+    pub avg_prior_file_commits: f64,
+    /// Mean of the author's earlier commits to the repository, per change.
+    pub avg_prior_repo_commits: f64,
+    /// Share of changes that were low-experience, 0–100.
+    pub experience_score: f64,
+}
+
+/// Normalized Shannon entropy (0–1) of the changes in one period.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntropyPeriod {
+    /// First day of the period (YYYY-MM-DD).
+    pub start: String,
+    pub commit_count: usize,
+    pub file_count: usize,
+    pub entropy: f64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct BugData {
     pub bug_commits: usize,
@@ -55,6 +160,28 @@ pub struct SiloData {
     pub author_count: usize,
 }
 
+/// Who holds the knowledge of a file and whether they are still around.
+#[derive(Debug, Clone, Serialize)]
+pub struct OwnershipData {
+    /// Authors with at least a quarter of the file's commits, most commits first.
+    pub primary_authors: Vec<String>,
+    /// Days since the most recently active primary author last committed anywhere.
+    pub primary_last_active_days: i64,
+    /// Every primary author has been inactive for the configured period.
+    pub orphaned: bool,
+}
+
+/// Files that depend on one author's knowledge.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorRisk {
+    pub author: String,
+    /// Days since the author's last commit anywhere in the analyzed history.
+    pub last_active_days: i64,
+    pub inactive: bool,
+    /// Files where this author is the only primary author, most commits first.
+    pub files: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CommitQualityData {
     pub wip_commits: usize,
@@ -68,6 +195,48 @@ pub struct CouplingEntry {
     pub file_b: String,
     pub co_changes: usize,
     pub strength: f64,
+    /// `"frontend ↔ billing"` when the files sit in different configured
+    /// components; `None` within one component or without `boundaries`.
+    pub boundary: Option<String>,
+    /// The crossing is declared in `boundaries.allow`.
+    pub boundary_allowed: bool,
+}
+
+impl CouplingEntry {
+    /// Crosses a component boundary without a declared dependency.
+    pub fn is_boundary_violation(&self) -> bool {
+        self.boundary.is_some() && !self.boundary_allowed
+    }
+}
+
+/// A file that changed together with the file given to `git-scanline coupled`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoupledFile {
+    pub file: String,
+    /// Change sets touching both files.
+    pub support: usize,
+    /// Share (%) of the queried file's change sets that also touched this file.
+    pub confidence: f64,
+    /// Share (%) of this file's change sets that also touched the queried file.
+    pub reverse_confidence: f64,
+    /// Jaccard strength (%), as in the hotspot report's coupling.
+    pub strength: f64,
+}
+
+/// A group of files connected by coupling at or above the cluster threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct CouplingCluster {
+    /// Deepest directory shared by every member, or the best-connected file.
+    pub name: String,
+    /// Members, best-connected first.
+    pub files: Vec<String>,
+    /// Mean Jaccard strength over every member pair (0–100); unlinked pairs
+    /// count as 0, so 100 means every pair always changes together.
+    pub cohesion: f64,
+    /// Distinct parent directories of the members.
+    pub directories: Vec<String>,
+    /// Members live under more than one top-level directory.
+    pub cross_component: bool,
 }
 
 // ─── Security ─────────────────────────────────────────────────────────────────
@@ -76,9 +245,43 @@ pub struct CouplingEntry {
 pub struct SecurityRisk {
     pub file: String,
     pub risk_type: String,
+    pub severity: String,
     pub commit_count: usize,
     pub first_seen: String,
     pub last_seen: String,
+    /// Whether the file exists in the HEAD tree.
+    pub present_at_head: bool,
+    /// Branches and tags whose tip tree still contains the file.
+    pub refs: Vec<String>,
+    /// Commits (any ref) that added the file.
+    pub added_in: Vec<String>,
+    /// Commits (any ref) that deleted the file.
+    pub removed_in: Vec<String>,
+    /// Authors of the commits that touched the file, most active first.
+    pub authors: Vec<String>,
+}
+
+/// A credential found in the *content* of a line added in history.
+/// Only a redacted form of the secret is ever stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretFinding {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub file: String,
+    pub line: usize,
+    pub rule: String,
+    pub redacted: String,
+    pub present_at_head: bool,
+}
+
+/// A file excluded from scoring because its content at HEAD looks
+/// machine-generated, minified or vendored.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedFile {
+    pub file: String,
+    /// One of: generated, minified, vendored.
+    pub reason: String,
 }
 
 // ─── Scoring ──────────────────────────────────────────────────────────────────
@@ -102,6 +305,32 @@ impl std::fmt::Display for Tier {
     }
 }
 
+/// Position in the classic churn × complexity quadrant, split at the median
+/// of each signal across analyzed files.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Quadrant {
+    /// High churn, high complexity — refactor first.
+    Hotspot,
+    /// High churn, low complexity — busy but easy to change.
+    ActiveSimple,
+    /// Low churn, high complexity — leave alone until it needs to change.
+    StableComplex,
+    /// Low churn, low complexity.
+    Healthy,
+}
+
+impl std::fmt::Display for Quadrant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Quadrant::Hotspot => write!(f, "hotspot"),
+            Quadrant::ActiveSimple => write!(f, "active-simple"),
+            Quadrant::StableComplex => write!(f, "stable-complex"),
+            Quadrant::Healthy => write!(f, "healthy"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct HotspotDetails {
     pub commit_count: usize,
@@ -115,6 +344,51 @@ pub struct HotspotDetails {
     pub author_count: usize,
     pub additions: usize,
     pub deletions: usize,
+    pub loc: usize,
+    pub max_indent: usize,
+    pub longest_function: usize,
+    /// Test pairing; `None` for test files and non-code files.
+    pub tests: Option<TestCoevolution>,
+    /// `None` for files with no commits in the analyzed history.
+    pub age: Option<CodeAgeData>,
+    /// `None` for files with no commits in the analyzed history.
+    pub experience: Option<ExperienceData>,
+    /// Primary authors and whether they have all gone inactive.
+    pub ownership: Option<OwnershipData>,
+    /// Commits and authors; computed for reported results only.
+    pub history: Option<FileHistory>,
+    /// Files coupled with this one, highest confidence first; reported results only.
+    pub coupled: Vec<CoupledFile>,
+}
+
+/// A commit that touched a reported file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCommit {
+    pub hash: String,
+    pub author: String,
+    /// Author date (YYYY-MM-DD, UTC).
+    pub date: String,
+    pub subject: String,
+    pub bug_fix: bool,
+    pub revert: bool,
+}
+
+/// One author's share of a file's commits.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorShare {
+    pub author: String,
+    pub commits: usize,
+    /// 0–100.
+    pub percent: f64,
+}
+
+/// Change history of a reported file, for the HTML drill-down.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileHistory {
+    /// Newest first, at most 50.
+    pub commits: Vec<FileCommit>,
+    /// Most commits first; counts every commit in the analyzed history.
+    pub authors: Vec<AuthorShare>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -128,11 +402,38 @@ pub struct HotspotResult {
     pub coupling_score: f64,
     pub silo_score: f64,
     pub commit_quality_score: f64,
+    pub complexity_score: f64,
+    pub test_gap_score: f64,
+    /// Line coverage percentage; `None` without `--coverage` or when the
+    /// report does not mention the file.
+    pub line_coverage: Option<f64>,
+    /// `100 - line_coverage`; 0 when coverage is unknown.
+    pub coverage_gap_score: f64,
+    pub entropy_score: f64,
+    pub experience_score: f64,
+    /// Score was reduced because the file has been untouched for the
+    /// configured stable period.
+    pub stability_dampened: bool,
+    pub quadrant: Quadrant,
     pub tier: Tier,
     pub details: HotspotDetails,
 }
 
-#[derive(Debug, Clone)]
+/// A function or method ranked by the history of hunks inside it.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionHotspot {
+    pub file: String,
+    pub function: String,
+    pub hotspot_score: f64,
+    pub churn_score: f64,
+    pub bug_fix_score: f64,
+    pub revert_score: f64,
+    pub commit_count: usize,
+    pub bug_commits: usize,
+    pub revert_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Weights {
     pub churn: f64,
     pub bugs: f64,
@@ -141,6 +442,11 @@ pub struct Weights {
     pub coupling: f64,
     pub silo: f64,
     pub commit_quality: f64,
+    pub complexity: f64,
+    pub tests: f64,
+    pub coverage: f64,
+    pub entropy: f64,
+    pub experience: f64,
 }
 
 impl Default for Weights {
@@ -153,6 +459,15 @@ impl Default for Weights {
             coupling: 0.09,
             silo: 0.05,
             commit_quality: 0.09,
+            // Opt-in, like tests, entropy and experience, so default scores
+            // stay comparable across versions.
+            complexity: 0.0,
+            // Opt-in: untested code is not scored unless asked for.
+            tests: 0.0,
+            // Only counted when a coverage report is given.
+            coverage: 0.10,
+            entropy: 0.0,
+            experience: 0.0,
         }
     }
 }
@@ -171,7 +486,103 @@ pub struct ReportMeta {
 #[derive(Debug, Clone, Serialize)]
 pub struct Report {
     pub meta: ReportMeta,
+    /// Normalized weights the scores were computed with.
+    pub weights: Weights,
     pub results: Vec<HotspotResult>,
     pub couplings: Vec<CouplingEntry>,
+    /// Groups of three or more files that change together, largest first.
+    pub coupling_clusters: Vec<CouplingCluster>,
+    /// Coupled pairs crossing an undeclared component boundary, most co-changes first.
+    pub boundary_violations: Vec<CouplingEntry>,
     pub security_risks: Vec<SecurityRisk>,
+    pub secret_findings: Vec<SecretFinding>,
+    /// Security risks and secret findings waived by the config allowlist.
+    pub security_suppressed: usize,
+    /// Files dropped by `--detect-generated`.
+    pub auto_excluded: Vec<GeneratedFile>,
+    /// Function-level ranking; empty unless `--functions` is set.
+    pub function_hotspots: Vec<FunctionHotspot>,
+    /// Change entropy per 30-day period, oldest first.
+    pub entropy_periods: Vec<EntropyPeriod>,
+    /// Authors who are the only primary author of some files, most files first.
+    pub knowledge_risks: Vec<AuthorRisk>,
+    /// Every scored file, for the HTML treemap and `--format svg`; the JSON
+    /// report already carries the shown results.
+    #[serde(skip)]
+    pub treemap: Treemap,
+}
+
+/// What a treemap rectangle's area stands for.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TreemapSize {
+    /// Lines added plus deleted in the analyzed history.
+    #[default]
+    Churn,
+    /// Lines of code at HEAD.
+    Loc,
+}
+
+impl TreemapSize {
+    pub fn label(self) -> &'static str {
+        match self {
+            TreemapSize::Churn => "lines changed",
+            TreemapSize::Loc => "lines of code",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Treemap {
+    pub size_by: TreemapSize,
+    pub files: Vec<TreemapFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TreemapFile {
+    pub file: String,
+    pub size: usize,
+    pub score: f64,
+}
+
+// ─── Authors Report ───────────────────────────────────────────────────────────
+
+/// One author's (or team's) contribution, from `git-scanline authors`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorStats {
+    /// Author email, team name, or pseudonym with `--anonymize`.
+    pub name: String,
+    /// Authors grouped into a team; empty per author.
+    pub members: Vec<String>,
+    pub commits: usize,
+    pub files_touched: usize,
+    /// Reported hotspots whose top author this is.
+    pub hotspots_owned: usize,
+    pub bug_fix_commits: usize,
+    pub reverts_authored: usize,
+    pub wip_commits: usize,
+    pub wip_ratio: f64,
+    /// Mean number of files changed per commit.
+    pub avg_commit_size: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorReport {
+    pub meta: ReportMeta,
+    /// "author" or "team".
+    pub group_by: String,
+    pub authors: Vec<AuthorStats>,
+}
+
+// ─── Coupling Query ───────────────────────────────────────────────────────────
+
+/// Files that usually change with one file, from `git-scanline coupled`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CouplingQuery {
+    pub meta: ReportMeta,
+    pub file: String,
+    /// Change sets touching the queried file.
+    pub change_sets: usize,
+    /// Same-author commits within this many hours were merged into one change set.
+    pub window_hours: Option<u32>,
+    pub coupled: Vec<CoupledFile>,
 }